@@ -0,0 +1,111 @@
+use std::{fs, path::Path};
+
+use rorm::{error::Result, DateTime, MigrationState, Migrator, Timestamp};
+
+use crate::{connect, MigrateArgs, MigrateCommand};
+
+pub async fn run(url: Option<&str>, args: MigrateArgs) -> Result<()> {
+    let MigrateArgs { dir, command } = args;
+    if let MigrateCommand::Create { name } = &command {
+        return create(&dir, name);
+    }
+
+    let conn = rorm::Connection::from(connect(url).await?);
+    let migrator = Migrator::from_dir(&dir)?;
+
+    match command {
+        MigrateCommand::Create { .. } => unreachable!(),
+        MigrateCommand::Run => {
+            let versions = migrator.migrate_up(&conn).await?;
+            println!("Applied {} migration(s) {:?}", versions.len(), versions);
+        }
+        MigrateCommand::Revert { target } => {
+            let target = match target {
+                Some(target) => target,
+                None => {
+                    // Version before the last applied one
+                    let applied = migrator
+                        .status(&conn)
+                        .await?
+                        .into_iter()
+                        .filter(|status| status.state != MigrationState::Pending)
+                        .map(|status| status.version)
+                        .collect::<Vec<_>>();
+                    if applied.is_empty() {
+                        println!("No migration to revert");
+                        return Ok(());
+                    }
+
+                    applied.len().checked_sub(2).map_or(0, |i| applied[i])
+                }
+            };
+
+            let versions = migrator.migrate_down(&conn, target).await?;
+            println!("Reverted {} migration(s) {:?}", versions.len(), versions);
+        }
+        MigrateCommand::Status => {
+            for status in migrator.status(&conn).await? {
+                let state = match status.state {
+                    MigrationState::Pending => "pending",
+                    MigrationState::Applied => "applied",
+                    MigrationState::ChecksumMismatch => "checksum mismatch",
+                    MigrationState::Missing => "missing",
+                };
+                println!(
+                    "{:<16} {:<40} {:<20} {}",
+                    status.version,
+                    status.name,
+                    state,
+                    status
+                        .applied_at
+                        .map(|at| at.to_string())
+                        .unwrap_or_default()
+                );
+            }
+        }
+        MigrateCommand::Unlock => {
+            migrator.force_unlock(&conn).await?;
+            println!("Unlocked");
+        }
+    }
+
+    Ok(())
+}
+
+/// Create empty up and down sql files named by current UTC time
+fn create(dir: &Path, name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(rorm::error::argument!(
+            "Invalid migration name `{}`, only letters, digits and `_` are allowed",
+            name
+        ));
+    }
+
+    fs::create_dir_all(dir)
+        .map_err(|e| rorm::error::migration!("Create dir `{}` error: {}", dir.display(), e))?;
+
+    let version = utc_version();
+    for (kind, content) in [
+        ("up", "-- Add migration script here\n"),
+        ("down", "-- Revert migration script here\n"),
+    ] {
+        let path = dir.join(format!("{}_{}.{}.sql", version, name, kind));
+        fs::write(&path, content)
+            .map_err(|e| rorm::error::migration!("Create `{}` error: {}", path.display(), e))?;
+        println!("Created {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Current UTC time as `YYYYMMDDhhmmss`
+fn utc_version() -> u64 {
+    let DateTime { date, time } = Timestamp::now().to_datetime();
+
+    date.year as u64 * 10_000_000_000
+        + date.month as u64 * 100_000_000
+        + date.day as u64 * 1_000_000
+        + time.hour as u64 * 10_000
+        + time.minute as u64 * 100
+        + time.second as u64
+}
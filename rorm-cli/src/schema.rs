@@ -0,0 +1,236 @@
+use std::{fs, path::Path};
+
+use rorm::{
+    error::Result, query::Dialect, ColumnDesc, IndexDesc, Migrator, SchemaChange, TableDesc, Value,
+};
+use rorm_conn::Connection;
+
+/// Bookkeeping tables of migrator
+const MIGRATION_TABLES: &[&str] = &["rorm_migrations", "rorm_migrations_lock"];
+
+pub async fn tables(conn: &Connection) -> Result<()> {
+    for table in conn.list_tables().await? {
+        println!("{}", table);
+    }
+
+    Ok(())
+}
+
+pub async fn describe(conn: &Connection, table: &str) -> Result<()> {
+    let desc = describe_table(conn, table).await?;
+
+    println!("Table `{}`", desc.name);
+    println!("  Columns:");
+    for col in &desc.columns {
+        println!("    {}", format_column(col));
+    }
+    if !desc.indexes.is_empty() {
+        println!("  Indexes:");
+        for idx in &desc.indexes {
+            println!("    {}", format_index(idx));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn ddl(conn: &Connection, tables: Vec<String>) -> Result<()> {
+    for table in or_all_tables(conn, tables).await? {
+        let desc = describe_table(conn, &table).await?;
+        for sql in conn.gen_ddl(&desc) {
+            println!("{};", sql);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn dump(conn: &Connection, tables: Vec<String>) -> Result<()> {
+    for table in or_all_tables(conn, tables).await? {
+        let desc = describe_table(conn, &table).await?;
+        for sql in conn.gen_ddl(&desc) {
+            println!("{};", sql);
+        }
+
+        let names = desc
+            .columns
+            .iter()
+            .map(|col| col.name.as_str())
+            .collect::<Vec<_>>();
+        let sql = format!("SELECT {} FROM {}", names.join(", "), desc.name);
+        let dialect = conn.dialect();
+        let rows = conn
+            .query_many_map(&sql, vec![], |row| {
+                let values = names
+                    .iter()
+                    .map(|name| value_to_sql(row.value(name), dialect))
+                    .collect::<Result<Vec<_>>>();
+                async move { values }
+            })
+            .await?;
+        for values in rows {
+            println!(
+                "INSERT INTO {} ({}) VALUES ({});",
+                desc.name,
+                names.join(", "),
+                values.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn entities(conn: &Connection, tables: Vec<String>, output: Option<&Path>) -> Result<()> {
+    let tables = if tables.is_empty() {
+        user_tables(conn).await?
+    } else {
+        tables
+    };
+    let src = rorm::gen_entities(&rorm::Connection::from(conn.clone()), &tables).await?;
+
+    match output {
+        Some(path) => fs::write(path, src)
+            .map_err(|e| rorm::error::argument!("Write `{}` error: {}", path.display(), e)),
+        None => {
+            print!("{}", src);
+            Ok(())
+        }
+    }
+}
+
+/// Run migrations on an empty shadow database, then compare every table of it with the database,
+/// fails if any table differs
+pub async fn drift(url: &str, shadow_url: Option<&str>, dir: &Path) -> Result<()> {
+    let scheme = |url: &str| url.split("://").next().unwrap_or_default().to_string();
+    let shadow_url = match shadow_url {
+        Some(shadow_url) => shadow_url.to_string(),
+        None if scheme(url) == "sqlite" => "sqlite://memory".to_string(),
+        None => {
+            return Err(rorm::error::argument!(
+                "Shadow database url is required for `{}`",
+                scheme(url)
+            ))
+        }
+    };
+    if scheme(url) != scheme(&shadow_url) {
+        return Err(rorm::error::argument!(
+            "Shadow database must be the same kind as database"
+        ));
+    }
+
+    let conn = Connection::connect(url).await?;
+    let shadow = Connection::connect(&shadow_url).await?;
+    let migrator = Migrator::from_dir(dir)?;
+    migrator
+        .migrate_up(&rorm::Connection::from(shadow.clone()))
+        .await?;
+
+    let mut is_drifted = false;
+    let expected_tables = user_tables(&shadow).await?;
+    let actual_tables = user_tables(&conn).await?;
+    for table in &expected_tables {
+        if !actual_tables.contains(table) {
+            is_drifted = true;
+            println!("Table `{}` is missing", table);
+            continue;
+        }
+
+        let expected = describe_table(&shadow, table).await?;
+        let changes = conn.diff_table(&expected).await?;
+        if !changes.is_empty() {
+            is_drifted = true;
+            println!("Table `{}` differs:", table);
+            for change in &changes {
+                println!("  {}", format_change(change));
+            }
+        }
+    }
+    for table in &actual_tables {
+        if !expected_tables.contains(table) {
+            is_drifted = true;
+            println!("Table `{}` is not created by migrations", table);
+        }
+    }
+
+    // Fails so that drift exits with non zero code, e.g. in CI
+    if is_drifted {
+        return Err(rorm::error::migration!("Schema drifts from migrations"));
+    }
+    println!("No drift");
+
+    Ok(())
+}
+
+async fn describe_table(conn: &Connection, table: &str) -> Result<TableDesc> {
+    conn.describe_table(table)
+        .await?
+        .ok_or_else(|| rorm::error::argument!("Table `{}` not found", table))
+}
+
+async fn or_all_tables(conn: &Connection, tables: Vec<String>) -> Result<Vec<String>> {
+    if tables.is_empty() {
+        conn.list_tables().await
+    } else {
+        Ok(tables)
+    }
+}
+
+async fn user_tables(conn: &Connection) -> Result<Vec<String>> {
+    Ok(conn
+        .list_tables()
+        .await?
+        .into_iter()
+        .filter(|table| !MIGRATION_TABLES.contains(&table.as_str()))
+        .collect())
+}
+
+/// `+` means missing in database, `-` means not created by migrations
+fn format_change(change: &SchemaChange) -> String {
+    match change {
+        SchemaChange::AddColumn(col) => format!("+ column {}", format_column(col)),
+        SchemaChange::AlterColumn { from, to } => {
+            format!("~ column {} => {}", format_column(from), format_column(to))
+        }
+        SchemaChange::DropColumn(col) => format!("- column {}", format_column(col)),
+        SchemaChange::AddIndex(idx) => format!("+ index {}", format_index(idx)),
+        SchemaChange::AlterIndex { from, to } => {
+            format!("~ index {} => {}", format_index(from), format_index(to))
+        }
+        SchemaChange::DropIndex(idx) => format!("- index {}", format_index(idx)),
+    }
+}
+
+fn format_column(col: &ColumnDesc) -> String {
+    let mut parts = vec![col.name.clone(), format!("{:?}", col.ty)];
+    if col.is_primary_key {
+        parts.push("PRIMARY KEY".into());
+    }
+    if col.is_auto_increment {
+        parts.push("AUTO_INCREMENT".into());
+    }
+    if col.is_not_null {
+        parts.push("NOT NULL".into());
+    }
+    if let Some(default) = &col.default {
+        parts.push(format!("DEFAULT {}", default));
+    }
+    if col.is_unique {
+        parts.push("UNIQUE".into());
+    }
+
+    parts.join(" ")
+}
+
+fn format_index(idx: &IndexDesc) -> String {
+    format!(
+        "{}{} ({})",
+        if idx.is_unique { "UNIQUE " } else { "" },
+        idx.name,
+        idx.keys.join(", ")
+    )
+}
+
+fn value_to_sql(value: Option<&Value>, dialect: Dialect) -> Result<String> {
+    value.map_or_else(|| Ok("NULL".into()), |v| v.to_sql_literal(dialect))
+}
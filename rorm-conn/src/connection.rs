@@ -1,6 +1,9 @@
 use std::{future::Future, sync::Arc};
 
-use crate::{Driver, Result, Row, TableInfo, Value};
+use crate::{
+    Dialect, Driver, ForeignKeyDesc, Result, Row, SchemaChange, SchemaDiff, SyncMode, TableDesc,
+    TableInfo, Value,
+};
 
 #[derive(Clone)]
 pub struct Connection {
@@ -92,6 +95,70 @@ impl Connection {
         Ok(())
     }
 
+    /// # List names of all tables in database
+    pub async fn list_tables(&self) -> Result<Vec<String>> {
+        self.driver.list_tables().await
+    }
+
+    /// # Read table description from database
+    ///
+    /// Return `None` if table not exists
+    pub async fn describe_table(&self, name: &str) -> Result<Option<TableDesc>> {
+        self.driver.describe_table(name).await
+    }
+
+    /// # Read foreign keys of table from database
+    pub async fn list_foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKeyDesc>> {
+        self.driver.list_foreign_keys(table_name).await
+    }
+
+    /// # Create table if not exists, then sync columns and indexes with table info
+    ///
+    /// Missing columns and indexes are always added, type changes and drops are
+    /// applied only in `SyncMode::Destructive`, otherwise they are returned as pending
+    pub async fn sync_table(&self, info: &TableInfo, mode: SyncMode) -> Result<SchemaDiff> {
+        self.driver.sync_table(info, mode).await
+    }
+
+    /// # Drop table, return error if table not exists
+    pub async fn drop_table(&self, name: &str) -> Result<()> {
+        self.driver.drop_table(name, false).await
+    }
+
+    /// # Drop table if exists
+    pub async fn drop_table_if_exists(&self, name: &str) -> Result<()> {
+        self.driver.drop_table(name, true).await
+    }
+
+    /// # Delete all rows and reset auto increment counter
+    ///
+    /// Sqlite deletes rows and resets `sqlite_sequence`, mysql uses `TRUNCATE TABLE`
+    pub async fn truncate_table(&self, name: &str) -> Result<()> {
+        self.driver.truncate_table(name).await
+    }
+
+    /// # Rename table, indexes keep their names
+    pub async fn rename_table(&self, from: &str, to: &str) -> Result<()> {
+        self.driver.rename_table(from, to).await
+    }
+
+    /// # Generate changes that turn the live table into `expected`
+    ///
+    /// Every column and index is added if table not exists
+    pub async fn diff_table(&self, expected: &TableDesc) -> Result<Vec<SchemaChange>> {
+        self.driver.diff_table(expected).await
+    }
+
+    /// # Generate create table and create index sqls in the dialect of database
+    pub fn gen_ddl(&self, desc: &TableDesc) -> Vec<String> {
+        self.driver.gen_ddl(desc)
+    }
+
+    /// # Database of the connection, to build sqls differing between databases
+    pub fn dialect(&self) -> Dialect {
+        self.driver.dialect()
+    }
+
     #[cfg(feature = "sqlite")]
     fn connect_sqlite(url: &str) -> Result<Self> {
         use std::path::Path;
@@ -109,6 +176,7 @@ impl Connection {
             rusqlite::Connection::open(path)
                 .map_err(|e| rorm_error::connection!("Sqlite open `{}` error: {}", path, e))?
         };
+        crate::drivers::sqlite::register_functions(&conn)?;
         let driver = crate::drivers::sqlite::SqliteConnProxy::new(conn);
 
         Ok(Self {
@@ -148,4 +216,44 @@ impl Driver for DummyDriver {
     async fn init_table(&self, _info: &TableInfo) -> Result<()> {
         unreachable!()
     }
+
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        unreachable!()
+    }
+
+    async fn describe_table(&self, _name: &str) -> Result<Option<TableDesc>> {
+        unreachable!()
+    }
+
+    async fn list_foreign_keys(&self, _table_name: &str) -> Result<Vec<ForeignKeyDesc>> {
+        unreachable!()
+    }
+
+    async fn sync_table(&self, _info: &TableInfo, _mode: SyncMode) -> Result<SchemaDiff> {
+        unreachable!()
+    }
+
+    async fn drop_table(&self, _name: &str, _if_exists: bool) -> Result<()> {
+        unreachable!()
+    }
+
+    async fn truncate_table(&self, _name: &str) -> Result<()> {
+        unreachable!()
+    }
+
+    async fn rename_table(&self, _from: &str, _to: &str) -> Result<()> {
+        unreachable!()
+    }
+
+    async fn diff_table(&self, _expected: &TableDesc) -> Result<Vec<SchemaChange>> {
+        unreachable!()
+    }
+
+    fn gen_ddl(&self, _desc: &TableDesc) -> Vec<String> {
+        unreachable!()
+    }
+
+    fn dialect(&self) -> Dialect {
+        unreachable!()
+    }
 }
@@ -0,0 +1,430 @@
+//! # Date and time
+//!
+//! Database independent date and time values. Types of `chrono` and `time` are
+//! converted through them when the corresponding feature is enabled.
+//!
+//! | Type      | SQLite                         | MySQL    |
+//! |-----------|--------------------------------|----------|
+//! | Date      | TEXT `YYYY-MM-DD`              | DATE     |
+//! | Time      | TEXT `hh:mm:ss[.f]`            | TIME     |
+//! | DateTime  | TEXT `YYYY-MM-DD hh:mm:ss[.f]` | DATETIME |
+//! | Timestamp | INTEGER, unix seconds          | DATETIME |
+
+#[cfg(feature = "chrono")]
+mod with_chrono;
+#[cfg(feature = "time")]
+mod with_time;
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rorm_error::Result;
+
+use crate::{FromValue, ToValue, Value};
+
+const SECS_PER_DAY: i64 = 86400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Calendar date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8, // 1 - 12
+    pub day: u8,   // 1 - 31
+}
+
+/// Time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Time {
+    pub hour: u8,        // 0 - 23
+    pub minute: u8,      // 0 - 59
+    pub second: u8,      // 0 - 59
+    pub nanosecond: u32, // 0 - 999_999_999
+}
+
+/// Date and time without time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+/// Instant in UTC, sub-second part is dropped when stored as unix seconds in sqlite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp {
+    pub secs: i64,  // Seconds since 1970-01-01 00:00:00 UTC
+    pub nanos: u32, // 0 - 999_999_999
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(rorm_error::argument!(
+                "Invalid date: {}-{}-{}",
+                year,
+                month,
+                day
+            ));
+        }
+
+        Ok(Self { year, month, day })
+    }
+
+    /// Days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    pub fn to_days(&self) -> i64 {
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146097 + doe - 719468
+    }
+
+    /// Date from days since 1970-01-01
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl Time {
+    pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Result<Self> {
+        if hour > 23 || minute > 59 || second > 59 || nanosecond >= NANOS_PER_SEC {
+            return Err(rorm_error::argument!(
+                "Invalid time: {}:{}:{}.{}",
+                hour,
+                minute,
+                second,
+                nanosecond
+            ));
+        }
+
+        Ok(Self {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    }
+
+    /// Seconds since midnight
+    pub fn to_secs(&self) -> u32 {
+        self.hour as u32 * 3600 + self.minute as u32 * 60 + self.second as u32
+    }
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+}
+
+impl Timestamp {
+    pub fn new(secs: i64, nanos: u32) -> Result<Self> {
+        if nanos >= NANOS_PER_SEC {
+            return Err(rorm_error::argument!("Invalid nanos: {}", nanos));
+        }
+
+        Ok(Self { secs, nanos })
+    }
+
+    pub fn now() -> Self {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => Self {
+                secs: d.as_secs() as i64,
+                nanos: d.subsec_nanos(),
+            },
+            Err(e) => {
+                // Before 1970
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => Self {
+                        secs: -(d.as_secs() as i64),
+                        nanos: 0,
+                    },
+                    n => Self {
+                        secs: -(d.as_secs() as i64) - 1,
+                        nanos: NANOS_PER_SEC - n,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Date and time in UTC
+    pub fn to_datetime(&self) -> DateTime {
+        let secs = self.secs.rem_euclid(SECS_PER_DAY) as u32;
+
+        DateTime {
+            date: Date::from_days(self.secs.div_euclid(SECS_PER_DAY)),
+            time: Time {
+                hour: (secs / 3600) as u8,
+                minute: (secs % 3600 / 60) as u8,
+                second: (secs % 60) as u8,
+                nanosecond: self.nanos,
+            },
+        }
+    }
+
+    /// Treat date time as UTC
+    pub fn from_datetime(dt: &DateTime) -> Self {
+        Self {
+            secs: dt.date.to_days() * SECS_PER_DAY + dt.time.to_secs() as i64,
+            nanos: dt.time.nanosecond,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond != 0 {
+            let frac = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", frac.trim_end_matches('0'))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+/// Formatted as date time in UTC
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_datetime().fmt(f)
+    }
+}
+
+/// `YYYY-MM-DD`
+impl FromStr for Date {
+    type Err = rorm_error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || rorm_error::from_value!("Invalid date: {:?}", s);
+
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let year = next()?.parse::<i32>().map_err(|_| invalid())?;
+        let month = next()?.parse::<u8>().map_err(|_| invalid())?;
+        let day = next()?.parse::<u8>().map_err(|_| invalid())?;
+
+        Self::new(year, month, day).map_err(|_| invalid())
+    }
+}
+
+/// `hh:mm:ss[.f]`
+impl FromStr for Time {
+    type Err = rorm_error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || rorm_error::from_value!("Invalid time: {:?}", s);
+
+        let mut parts = s.trim().splitn(3, ':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let hour = next()?.parse::<u8>().map_err(|_| invalid())?;
+        let minute = next()?.parse::<u8>().map_err(|_| invalid())?;
+        let second = next()?;
+        let (second, frac) = second.split_once('.').unwrap_or((second, ""));
+        let second = second.parse::<u8>().map_err(|_| invalid())?;
+        let nanosecond = if frac.is_empty() {
+            0
+        } else if frac.len() <= 9 && frac.chars().all(|c| c.is_ascii_digit()) {
+            format!("{:0<9}", frac)
+                .parse::<u32>()
+                .map_err(|_| invalid())?
+        } else {
+            return Err(invalid());
+        };
+
+        Self::new(hour, minute, second, nanosecond).map_err(|_| invalid())
+    }
+}
+
+/// `YYYY-MM-DD hh:mm:ss[.f]`, `T` is accepted as separator, time is midnight if omitted
+impl FromStr for DateTime {
+    type Err = rorm_error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        match s.split_once([' ', 'T']) {
+            Some((date, time)) => Ok(Self::new(date.parse()?, time.parse()?)),
+            None => Ok(Self::new(s.parse()?, Time::default())),
+        }
+    }
+}
+
+/// Date time in UTC, may end with `Z` or an offset such as `+08:00`
+impl FromStr for Timestamp {
+    type Err = rorm_error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || rorm_error::from_value!("Invalid timestamp: {:?}", s);
+
+        let s = s.trim();
+        let s = s.strip_suffix(['Z', 'z']).unwrap_or(s);
+        // Offset only appears after the time part
+        let (s, offset) = match s.get(10..).and_then(|time| time.rfind(['+', '-'])) {
+            Some(i) => {
+                let (s, offset) = s.split_at(i + 10);
+                let sign = if offset.starts_with('-') { -1 } else { 1 };
+                let (hour, minute) = offset[1..].split_once(':').ok_or_else(invalid)?;
+                let hour = hour.parse::<i64>().map_err(|_| invalid())?;
+                let minute = minute.parse::<i64>().map_err(|_| invalid())?;
+                (s.trim_end(), sign * (hour * 3600 + minute * 60))
+            }
+            None => (s, 0),
+        };
+
+        let mut ts = Self::from_datetime(&s.parse::<DateTime>().map_err(|_| invalid())?);
+        ts.secs -= offset;
+
+        Ok(ts)
+    }
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Text and bytes are parsed, mysql returns them in text protocol
+fn parse_value<T: FromStr<Err = rorm_error::Error>>(v: &Value) -> Option<Result<T>> {
+    match v {
+        Value::Str(s) => Some(s.parse()),
+        Value::Bytes(b) => Some(String::from_utf8_lossy(b).parse()),
+        _ => None,
+    }
+}
+
+impl ToValue for Date {
+    fn to_value(&self) -> Value {
+        Value::Date(*self)
+    }
+}
+
+impl ToValue for Time {
+    fn to_value(&self) -> Value {
+        Value::Time(*self)
+    }
+}
+
+impl ToValue for DateTime {
+    fn to_value(&self) -> Value {
+        Value::DateTime(*self)
+    }
+}
+
+impl ToValue for Timestamp {
+    fn to_value(&self) -> Value {
+        Value::Timestamp(*self)
+    }
+}
+
+impl FromValue for Date {
+    type Output = Date;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::Date(v) => Ok(*v),
+            Value::DateTime(v) => Ok(v.date),
+            _ => match parse_value::<DateTime>(v) {
+                Some(dt) => Ok(dt?.date),
+                None => Err(rorm_error::from_value!(
+                    "Invalid value: {:?}, output type: Date",
+                    v
+                )),
+            },
+        }
+    }
+}
+
+impl FromValue for Time {
+    type Output = Time;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::Time(v) => Ok(*v),
+            _ => parse_value(v).unwrap_or_else(|| {
+                Err(rorm_error::from_value!(
+                    "Invalid value: {:?}, output type: Time",
+                    v
+                ))
+            }),
+        }
+    }
+}
+
+impl FromValue for DateTime {
+    type Output = DateTime;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::DateTime(v) => Ok(*v),
+            Value::Date(v) => Ok(DateTime::new(*v, Time::default())),
+            Value::Timestamp(v) => Ok(v.to_datetime()),
+            _ => parse_value(v).unwrap_or_else(|| {
+                Err(rorm_error::from_value!(
+                    "Invalid value: {:?}, output type: DateTime",
+                    v
+                ))
+            }),
+        }
+    }
+}
+
+impl FromValue for Timestamp {
+    type Output = Timestamp;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::Timestamp(v) => Ok(*v),
+            Value::DateTime(v) => Ok(Timestamp::from_datetime(v)),
+            Value::I64(v) => Ok(Timestamp { secs: *v, nanos: 0 }),
+            Value::U64(v) => Ok(Timestamp {
+                secs: i64::try_from(*v).map_err(|_| {
+                    rorm_error::from_value!("Invalid value: {:?}, output type: Timestamp", v)
+                })?,
+                nanos: 0,
+            }),
+            _ => parse_value(v).unwrap_or_else(|| {
+                Err(rorm_error::from_value!(
+                    "Invalid value: {:?}, output type: Timestamp",
+                    v
+                ))
+            }),
+        }
+    }
+}
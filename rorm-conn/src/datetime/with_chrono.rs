@@ -0,0 +1,113 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use rorm_error::Result;
+
+use super::{Date, DateTime, Time, Timestamp};
+use crate::{db_type::impl_db_type, ColumnType, FromValue, ToValue, Value};
+
+fn to_date(v: &NaiveDate) -> Date {
+    Date {
+        year: v.year(),
+        month: v.month() as u8,
+        day: v.day() as u8,
+    }
+}
+
+fn to_time(v: &NaiveTime) -> Time {
+    Time {
+        hour: v.hour() as u8,
+        minute: v.minute() as u8,
+        second: v.second() as u8,
+        // Leap second is represented by nanosecond >= 1_000_000_000
+        nanosecond: v.nanosecond().min(999_999_999),
+    }
+}
+
+fn from_date(v: &Date) -> Result<NaiveDate> {
+    NaiveDate::from_ymd_opt(v.year, v.month as u32, v.day as u32)
+        .ok_or_else(|| rorm_error::from_value!("Invalid value: {:?}, output type: NaiveDate", v))
+}
+
+fn from_time(v: &Time) -> Result<NaiveTime> {
+    NaiveTime::from_hms_nano_opt(
+        v.hour as u32,
+        v.minute as u32,
+        v.second as u32,
+        v.nanosecond,
+    )
+    .ok_or_else(|| rorm_error::from_value!("Invalid value: {:?}, output type: NaiveTime", v))
+}
+
+impl ToValue for NaiveDate {
+    fn to_value(&self) -> Value {
+        Value::Date(to_date(self))
+    }
+}
+
+impl ToValue for NaiveTime {
+    fn to_value(&self) -> Value {
+        Value::Time(to_time(self))
+    }
+}
+
+impl ToValue for NaiveDateTime {
+    fn to_value(&self) -> Value {
+        Value::DateTime(DateTime::new(to_date(&self.date()), to_time(&self.time())))
+    }
+}
+
+impl<Tz: TimeZone> ToValue for chrono::DateTime<Tz> {
+    fn to_value(&self) -> Value {
+        Value::Timestamp(Timestamp {
+            secs: self.timestamp(),
+            nanos: self.timestamp_subsec_nanos().min(999_999_999),
+        })
+    }
+}
+
+impl FromValue for NaiveDate {
+    type Output = NaiveDate;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        from_date(&Date::from_value(v)?)
+    }
+}
+
+impl FromValue for NaiveTime {
+    type Output = NaiveTime;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        from_time(&Time::from_value(v)?)
+    }
+}
+
+impl FromValue for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let dt = DateTime::from_value(v)?;
+
+        Ok(NaiveDateTime::new(
+            from_date(&dt.date)?,
+            from_time(&dt.time)?,
+        ))
+    }
+}
+
+impl FromValue for chrono::DateTime<Utc> {
+    type Output = chrono::DateTime<Utc>;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let ts = Timestamp::from_value(v)?;
+
+        Utc.timestamp_opt(ts.secs, ts.nanos)
+            .single()
+            .ok_or_else(|| {
+                rorm_error::from_value!("Invalid value: {:?}, output type: DateTime<Utc>", v)
+            })
+    }
+}
+
+impl_db_type! {NaiveDate, ColumnType::Date}
+impl_db_type! {NaiveTime, ColumnType::Time}
+impl_db_type! {NaiveDateTime, ColumnType::DateTime}
+impl_db_type! {chrono::DateTime<Utc>, ColumnType::Timestamp}
@@ -0,0 +1,110 @@
+use rorm_error::Result;
+use time::{Month, OffsetDateTime, PrimitiveDateTime};
+
+use super::{Date, DateTime, Time, Timestamp};
+use crate::{db_type::impl_db_type, ColumnType, FromValue, ToValue, Value};
+
+fn to_date(v: &time::Date) -> Date {
+    Date {
+        year: v.year(),
+        month: v.month() as u8,
+        day: v.day(),
+    }
+}
+
+fn to_time(v: &time::Time) -> Time {
+    Time {
+        hour: v.hour(),
+        minute: v.minute(),
+        second: v.second(),
+        nanosecond: v.nanosecond(),
+    }
+}
+
+fn from_date(v: &Date) -> Result<time::Date> {
+    Month::try_from(v.month)
+        .and_then(|month| time::Date::from_calendar_date(v.year, month, v.day))
+        .map_err(|e| rorm_error::from_value!("Invalid value: {:?}, output type: Date, {}", v, e))
+}
+
+fn from_time(v: &Time) -> Result<time::Time> {
+    time::Time::from_hms_nano(v.hour, v.minute, v.second, v.nanosecond)
+        .map_err(|e| rorm_error::from_value!("Invalid value: {:?}, output type: Time, {}", v, e))
+}
+
+impl ToValue for time::Date {
+    fn to_value(&self) -> Value {
+        Value::Date(to_date(self))
+    }
+}
+
+impl ToValue for time::Time {
+    fn to_value(&self) -> Value {
+        Value::Time(to_time(self))
+    }
+}
+
+impl ToValue for PrimitiveDateTime {
+    fn to_value(&self) -> Value {
+        Value::DateTime(DateTime::new(to_date(&self.date()), to_time(&self.time())))
+    }
+}
+
+impl ToValue for OffsetDateTime {
+    fn to_value(&self) -> Value {
+        Value::Timestamp(Timestamp {
+            secs: self.unix_timestamp(),
+            nanos: self.nanosecond(),
+        })
+    }
+}
+
+impl FromValue for time::Date {
+    type Output = time::Date;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        from_date(&Date::from_value(v)?)
+    }
+}
+
+impl FromValue for time::Time {
+    type Output = time::Time;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        from_time(&Time::from_value(v)?)
+    }
+}
+
+impl FromValue for PrimitiveDateTime {
+    type Output = PrimitiveDateTime;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let dt = DateTime::from_value(v)?;
+
+        Ok(PrimitiveDateTime::new(
+            from_date(&dt.date)?,
+            from_time(&dt.time)?,
+        ))
+    }
+}
+
+/// Offset is always UTC
+impl FromValue for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let ts = Timestamp::from_value(v)?;
+
+        OffsetDateTime::from_unix_timestamp_nanos(
+            ts.secs as i128 * 1_000_000_000 + ts.nanos as i128,
+        )
+        .map_err(|e| {
+            rorm_error::from_value!("Invalid value: {:?}, output type: OffsetDateTime, {}", v, e)
+        })
+    }
+}
+
+impl_db_type! {time::Date, ColumnType::Date}
+impl_db_type! {time::Time, ColumnType::Time}
+impl_db_type! {PrimitiveDateTime, ColumnType::DateTime}
+impl_db_type! {OffsetDateTime, ColumnType::Timestamp}
@@ -0,0 +1,50 @@
+//! # Db type
+//!
+//! Rust types that can be used as a column of entity. The macro reads column type
+//! and nullability from the impl, so aliases and paths of these types are accepted,
+//! and newtypes can derive it from the inner type.
+
+use crate::{ColumnType, Date, DateTime, Decimal, FromValue, Time, Timestamp, ToValue};
+
+pub trait DbType: ToValue + FromValue<Output = Self> {
+    /// Column type, length, precision and scale can be overridden by attributes of column
+    const COLUMN_TYPE: ColumnType;
+
+    /// Only `Option` is nullable
+    const IS_NOT_NULL: bool = true;
+}
+
+macro_rules! impl_db_type {
+    ($ty:ty, $column_type:expr) => {
+        impl $crate::DbType for $ty {
+            const COLUMN_TYPE: $crate::ColumnType = $column_type;
+        }
+    };
+}
+
+#[allow(unused_imports)] // Used by optional integrations
+pub(crate) use impl_db_type;
+
+impl<T: DbType> DbType for Option<T> {
+    const COLUMN_TYPE: ColumnType = T::COLUMN_TYPE;
+    const IS_NOT_NULL: bool = false;
+}
+
+impl_db_type! {bool, ColumnType::Bool}
+impl_db_type! {i8, ColumnType::I8}
+impl_db_type! {u8, ColumnType::U8}
+impl_db_type! {i16, ColumnType::I16}
+impl_db_type! {u16, ColumnType::U16}
+impl_db_type! {i32, ColumnType::I32}
+impl_db_type! {u32, ColumnType::U32}
+impl_db_type! {i64, ColumnType::I64}
+impl_db_type! {u64, ColumnType::U64}
+impl_db_type! {f32, ColumnType::F32}
+impl_db_type! {f64, ColumnType::F64}
+impl_db_type! {String, ColumnType::Str(65535)}
+impl_db_type! {Vec<u8>, ColumnType::Bytes(65535)}
+impl_db_type! {Date, ColumnType::Date}
+impl_db_type! {Time, ColumnType::Time}
+impl_db_type! {DateTime, ColumnType::DateTime}
+impl_db_type! {Timestamp, ColumnType::Timestamp}
+impl_db_type! {Decimal, ColumnType::Decimal(38, 10)}
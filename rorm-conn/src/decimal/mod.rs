@@ -0,0 +1,188 @@
+//! # Decimal
+//!
+//! Exact decimal number, stored as `DECIMAL(p,s)` in mysql and as text in sqlite so
+//! no precision is lost. Type of `rust_decimal` is converted through it when the
+//! feature is enabled.
+
+#[cfg(feature = "rust_decimal")]
+mod with_rust_decimal;
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use rorm_error::Result;
+
+use crate::{FromValue, ToValue, Value};
+
+/// Value is `mantissa / 10^scale`, e.g. `12.30` is `{ mantissa: 1230, scale: 2 }`
+///
+/// Trailing zeros are ignored when compared, `12.30` equals `12.3`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Self { mantissa, scale }
+    }
+
+    /// Remove trailing zeros of fraction
+    pub fn normalize(&self) -> Self {
+        let mut v = *self;
+        while v.scale > 0 && v.mantissa % 10 == 0 {
+            v.mantissa /= 10;
+            v.scale -= 1;
+        }
+
+        v
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.normalize(), other.normalize());
+        a.mantissa == b.mantissa && a.scale == b.scale
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.normalize(), other.normalize());
+        // Sign decides unless both are positive or both are negative, zero is between them
+        let sign = a.mantissa.signum().cmp(&b.mantissa.signum());
+        if sign != Ordering::Equal || a.mantissa == 0 {
+            return sign;
+        }
+        if a.scale == b.scale {
+            return a.mantissa.cmp(&b.mantissa);
+        }
+
+        // Scale up the one with less fraction digits, it is non zero and its magnitude is greater
+        // if overflow
+        let (small, large) = if a.scale < b.scale { (a, b) } else { (b, a) };
+        let ord = match 10i128
+            .checked_pow(large.scale - small.scale)
+            .and_then(|n| small.mantissa.checked_mul(n))
+        {
+            Some(m) => m.cmp(&large.mantissa),
+            None if small.mantissa > 0 => Ordering::Greater,
+            None => Ordering::Less,
+        };
+
+        // Ordering of small to large
+        if a.scale < b.scale {
+            ord
+        } else {
+            ord.reverse()
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let v = self.normalize();
+        v.mantissa.hash(state);
+        v.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        // At least one digit before point
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+
+        if self.mantissa < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(int)?;
+        if !frac.is_empty() {
+            write!(f, ".{}", frac)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `[+-]digits[.digits]`, scale is the count of fraction digits
+impl FromStr for Decimal {
+    type Err = rorm_error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || rorm_error::from_value!("Invalid decimal: {:?}", s);
+
+        let t = s.trim();
+        let (is_negative, t) = match t.strip_prefix('-') {
+            Some(t) => (true, t),
+            None => (false, t.strip_prefix('+').unwrap_or(t)),
+        };
+        let (int, frac) = t.split_once('.').unwrap_or((t, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut mantissa = 0i128;
+        for c in int.chars().chain(frac.chars()) {
+            let digit = c.to_digit(10).ok_or_else(invalid)?;
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(digit as i128))
+                .ok_or_else(|| rorm_error::out_of_range!("Decimal out of range: {:?}", s))?;
+        }
+
+        Ok(Self {
+            mantissa: if is_negative { -mantissa } else { mantissa },
+            scale: frac.len() as u32,
+        })
+    }
+}
+
+impl ToValue for Decimal {
+    fn to_value(&self) -> Value {
+        Value::Decimal(*self)
+    }
+}
+
+impl FromValue for Decimal {
+    type Output = Decimal;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::Decimal(v) => Ok(*v),
+            Value::Str(s) => s.parse(),
+            // Mysql returns decimal as bytes
+            Value::Bytes(b) => String::from_utf8_lossy(b).parse(),
+            Value::U8(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::I8(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::U16(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::I16(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::U32(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::I32(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::U64(v) => Ok(Decimal::new(*v as i128, 0)),
+            Value::I64(v) => Ok(Decimal::new(*v as i128, 0)),
+            // Shortest representation that round trips, e.g. 0.1 => "0.1"
+            Value::F32(v) if v.is_finite() => v.to_string().parse(),
+            Value::F64(v) if v.is_finite() => v.to_string().parse(),
+            _ => Err(rorm_error::from_value!(
+                "Invalid value: {:?}, output type: Decimal",
+                v
+            )),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use rorm_error::Result;
+
+use super::Decimal;
+use crate::{db_type::impl_db_type, ColumnType, FromValue, ToValue, Value};
+
+impl ToValue for rust_decimal::Decimal {
+    fn to_value(&self) -> Value {
+        Value::Decimal(Decimal::new(self.mantissa(), self.scale()))
+    }
+}
+
+impl FromValue for rust_decimal::Decimal {
+    type Output = rust_decimal::Decimal;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let d = Decimal::from_value(v)?.normalize();
+
+        rust_decimal::Decimal::try_from_i128_with_scale(d.mantissa, d.scale).map_err(|e| {
+            rorm_error::out_of_range!(
+                "Invalid value: {:?}, output type: rust_decimal::Decimal, {}",
+                v,
+                e
+            )
+        })
+    }
+}
+
+impl_db_type! {rust_decimal::Decimal, ColumnType::Decimal(38, 10)}
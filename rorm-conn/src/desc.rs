@@ -0,0 +1,104 @@
+//! # Description
+//!
+//! Owned table description, read from a live database by `Connection::describe_table`,
+//! or converted from the static `TableInfo` generated by the macro.
+//!
+//! Column types are reported as the database sees them, so on sqlite every integer
+//! column except u64 is `ColumnType::I64` and every text column is `ColumnType::Str(65535)`.
+
+use crate::{ColumnInfo, ColumnType, TableInfo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableDesc {
+    pub name: String,
+    pub columns: Vec<ColumnDesc>,
+    pub indexes: Vec<IndexDesc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnDesc {
+    pub name: String,
+    pub ty: ColumnType,
+    pub is_primary_key: bool,
+    pub is_not_null: bool,
+    pub is_auto_increment: bool,
+    pub default: Option<String>, // Sql literal, number is "n", string is "'n'"
+    pub is_unique: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexDesc {
+    pub name: String,
+    pub keys: Vec<String>, // Column names
+    pub is_unique: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignKeyDesc {
+    pub columns: Vec<String>,
+    pub ref_table: String,
+    pub ref_columns: Vec<String>, // Same length as columns
+}
+
+impl TableDesc {
+    /// Find column by name
+    pub fn column(&self, name: &str) -> Option<&ColumnDesc> {
+        self.columns.iter().find(|col| col.name == name)
+    }
+
+    /// Find index by name
+    pub fn index(&self, name: &str) -> Option<&IndexDesc> {
+        self.indexes.iter().find(|idx| idx.name == name)
+    }
+}
+
+impl From<&TableInfo> for TableDesc {
+    fn from(info: &TableInfo) -> Self {
+        Self {
+            name: info.name.into(),
+            columns: flatten_columns(info.columns),
+            indexes: info
+                .indexes
+                .iter()
+                .map(|idx| IndexDesc {
+                    name: idx.name.into(),
+                    keys: idx.keys.iter().map(|k| k.column_name.into()).collect(),
+                    is_unique: false,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&ColumnInfo> for ColumnDesc {
+    fn from(info: &ColumnInfo) -> Self {
+        Self {
+            name: info.name.into(),
+            ty: info.ty.clone(),
+            is_primary_key: info.is_primary_key,
+            is_not_null: info.is_not_null,
+            is_auto_increment: info.is_auto_increment,
+            default: info.default.map(|def| def.into()),
+            is_unique: info.is_unique,
+        }
+    }
+}
+
+/// Expand flatten columns to the columns of reference table
+fn flatten_columns(infos: &[ColumnInfo]) -> Vec<ColumnDesc> {
+    let mut cols = vec![];
+
+    for info in infos {
+        if let Some(ref_info) = info.flatten_ref {
+            cols.extend(flatten_columns(ref_info.columns));
+        } else {
+            cols.push(ColumnDesc::from(info));
+        }
+    }
+
+    cols
+}
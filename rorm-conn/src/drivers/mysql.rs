@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     sync::{Arc, Mutex},
 };
@@ -12,7 +13,11 @@ use tokio_02::task::spawn_blocking;
 #[cfg(feature = "runtime-tokio-1")]
 use tokio_1::task::spawn_blocking;
 
-use crate::{ColumnInfo, ColumnType, Driver, IndexInfo, Row, TableInfo, Value};
+use crate::{
+    sync::{diff_table, split_changes},
+    ColumnDesc, ColumnType, Date, DateTime, Dialect, Driver, ForeignKeyDesc, IndexDesc, Row,
+    SchemaChange, SchemaDiff, SyncMode, TableDesc, TableInfo, Time, Value,
+};
 
 pub struct MysqlConnProxy {
     conn: Arc<Mutex<mysql_lib::Conn>>,
@@ -105,11 +110,12 @@ impl Driver for MysqlConnProxy {
 
     async fn init_table(&self, info: &TableInfo) -> Result<()> {
         // Generate sql
-        let table_sql = gen_create_table(info);
-        let _index_sqls = info
+        let desc = TableDesc::from(info);
+        let table_sql = gen_create_table(&desc);
+        let _index_sqls = desc
             .indexes
             .iter()
-            .map(|idx| gen_create_index(info.name, idx))
+            .map(|idx| gen_create_index(&desc.name, idx))
             .collect::<Vec<_>>();
 
         // Execute sql
@@ -139,9 +145,223 @@ impl Driver for MysqlConnProxy {
 
         Ok(())
     }
+
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        let rows = self
+            .query_many(
+                "SELECT TABLE_NAME AS name FROM information_schema.tables WHERE TABLE_SCHEMA = DATABASE() ORDER BY TABLE_NAME",
+                vec![],
+            )
+            .await?;
+
+        rows.iter().map(|row| row.get::<String>("name")).collect()
+    }
+
+    async fn describe_table(&self, name: &str) -> Result<Option<TableDesc>> {
+        // Get columns
+        let mut columns = vec![];
+        let rows = self
+            .query_many(
+                "SELECT COLUMN_NAME AS name, COLUMN_TYPE AS ty, DATA_TYPE AS data_ty, IS_NULLABLE AS nullable, \
+                 COLUMN_DEFAULT AS def, COLUMN_KEY AS col_key, EXTRA AS extra \
+                 FROM information_schema.columns WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+                 ORDER BY ORDINAL_POSITION",
+                vec![Value::Str(name.into())],
+            )
+            .await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        for row in rows {
+            let data_ty = row.get::<String>("data_ty")?.to_lowercase();
+            let col_key = row.get::<String>("col_key")?;
+            let is_text = data_ty.contains("char") || data_ty.contains("text");
+            columns.push(ColumnDesc {
+                name: row.get("name")?,
+                ty: mysql_type_to_column_type(&row.get::<String>("ty")?),
+                is_primary_key: col_key == "PRI",
+                is_not_null: row.get::<String>("nullable")? == "NO",
+                is_auto_increment: row
+                    .get::<String>("extra")?
+                    .to_lowercase()
+                    .contains("auto_increment"),
+                default: row.get::<Option<String>>("def")?.map(|def| {
+                    if is_text && !def.starts_with('\'') {
+                        format!("'{}'", def)
+                    } else {
+                        def
+                    }
+                }),
+                // Set by unique index of the column below
+                is_unique: false,
+            });
+        }
+
+        // Get indexes, unique index created by `UNIQUE` of column is named as the column and treated
+        // as column attribute, same as sqlite
+        let mut indexes = Vec::<IndexDesc>::new();
+        let rows = self
+            .query_many(
+                "SELECT INDEX_NAME AS name, NON_UNIQUE AS non_unique, COLUMN_NAME AS col \
+                 FROM information_schema.statistics WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+                 ORDER BY INDEX_NAME, SEQ_IN_INDEX",
+                vec![Value::Str(name.into())],
+            )
+            .await?;
+        for row in rows {
+            let index_name = row.get::<String>("name")?;
+            let col = row.get::<String>("col")?;
+            if index_name == "PRIMARY" {
+                continue;
+            }
+
+            if let Some(idx) = indexes.iter_mut().find(|idx| idx.name == index_name) {
+                idx.keys.push(col);
+            } else {
+                indexes.push(IndexDesc {
+                    name: index_name,
+                    keys: vec![col],
+                    is_unique: row.get::<i64>("non_unique")? == 0,
+                });
+            }
+        }
+        indexes.retain(|idx| {
+            if idx.is_unique && idx.keys.len() == 1 && idx.keys[0] == idx.name {
+                if let Some(col) = columns.iter_mut().find(|col| col.name == idx.name) {
+                    col.is_unique = true;
+                }
+                false
+            } else {
+                true
+            }
+        });
+
+        Ok(Some(TableDesc {
+            name: name.into(),
+            columns,
+            indexes,
+        }))
+    }
+
+    async fn list_foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKeyDesc>> {
+        let rows = self
+            .query_many(
+                "SELECT CONSTRAINT_NAME AS name, COLUMN_NAME AS col, REFERENCED_TABLE_NAME AS ref_table, \
+                 REFERENCED_COLUMN_NAME AS ref_col FROM information_schema.key_column_usage \
+                 WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND REFERENCED_TABLE_NAME IS NOT NULL \
+                 ORDER BY CONSTRAINT_NAME, ORDINAL_POSITION",
+                vec![Value::Str(table_name.into())],
+            )
+            .await?;
+
+        let mut names = Vec::<String>::new();
+        let mut foreign_keys = Vec::<ForeignKeyDesc>::new();
+        for row in rows {
+            let name = row.get::<String>("name")?;
+            let col = row.get::<String>("col")?;
+            let ref_col = row.get::<String>("ref_col")?;
+
+            match foreign_keys.last_mut() {
+                Some(fk) if names.last() == Some(&name) => {
+                    fk.columns.push(col);
+                    fk.ref_columns.push(ref_col);
+                }
+                _ => {
+                    names.push(name);
+                    foreign_keys.push(ForeignKeyDesc {
+                        columns: vec![col],
+                        ref_table: row.get("ref_table")?,
+                        ref_columns: vec![ref_col],
+                    });
+                }
+            }
+        }
+
+        Ok(foreign_keys)
+    }
+
+    async fn sync_table(&self, info: &TableInfo, mode: SyncMode) -> Result<SchemaDiff> {
+        self.init_table(info).await?;
+
+        let expected = TableDesc::from(info);
+        let actual = self
+            .describe_table(info.name)
+            .await?
+            .ok_or(rorm_error::database!("Table `{}` not found", info.name))?;
+
+        // Diff table
+        let changes = diff_table(&expected, &actual, is_same_type);
+        let diff = split_changes(changes, mode);
+        for change in &diff.pending {
+            log::warn!("Table `{}` has pending change: {:?}", info.name, change);
+        }
+
+        // Apply changes
+        let sqls = gen_sync_table(&actual.name, &diff.applied);
+        if !sqls.is_empty() {
+            self.execute_many(sqls.into_iter().map(|sql| (sql, vec![vec![]])).collect())
+                .await?;
+        }
+
+        Ok(diff)
+    }
+
+    async fn drop_table(&self, name: &str, if_exists: bool) -> Result<()> {
+        let sql = format!(
+            "DROP TABLE {if_exists}{name}",
+            if_exists = if if_exists { "IF EXISTS " } else { "" },
+            name = name
+        );
+        self.execute_many(vec![(sql, vec![vec![]])]).await?;
+
+        Ok(())
+    }
+
+    async fn truncate_table(&self, name: &str) -> Result<()> {
+        let sql = format!("TRUNCATE TABLE {}", name);
+        self.execute_many(vec![(sql, vec![vec![]])]).await?;
+
+        Ok(())
+    }
+
+    async fn rename_table(&self, from: &str, to: &str) -> Result<()> {
+        let sql = format!("RENAME TABLE {} TO {}", from, to);
+        self.execute_many(vec![(sql, vec![vec![]])]).await?;
+
+        Ok(())
+    }
+
+    async fn diff_table(&self, expected: &TableDesc) -> Result<Vec<SchemaChange>> {
+        let actual = self
+            .describe_table(&expected.name)
+            .await?
+            .unwrap_or_else(|| TableDesc {
+                name: expected.name.clone(),
+                columns: vec![],
+                indexes: vec![],
+            });
+
+        Ok(diff_table(expected, &actual, is_same_type))
+    }
+
+    fn gen_ddl(&self, desc: &TableDesc) -> Vec<String> {
+        let mut sqls = vec![gen_create_table(desc)];
+        sqls.extend(
+            desc.indexes
+                .iter()
+                .map(|idx| gen_create_index(&desc.name, idx)),
+        );
+
+        sqls
+    }
+
+    fn dialect(&self) -> Dialect {
+        Dialect::Mysql
+    }
 }
 
 fn mysql_row_to_rorm_row(src: mysql_lib::Row) -> Result<Row> {
+    let mut columns = vec![];
     let mut values = HashMap::new();
     let cols = src.columns_ref();
     for i in 0..src.len() {
@@ -162,6 +382,30 @@ fn mysql_row_to_rorm_row(src: mysql_lib::Row) -> Result<Row> {
                 mysql_lib::Value::UInt(v) => Value::U64(*v),
                 mysql_lib::Value::Float(v) => Value::F32(*v),
                 mysql_lib::Value::Double(v) => Value::F64(*v),
+                mysql_lib::Value::Date(year, month, day, hour, minute, second, micros) => {
+                    Value::DateTime(DateTime::new(
+                        Date {
+                            year: *year as i32,
+                            month: *month,
+                            day: *day,
+                        },
+                        Time {
+                            hour: *hour,
+                            minute: *minute,
+                            second: *second,
+                            nanosecond: *micros * 1000,
+                        },
+                    ))
+                }
+                // Time of day, negative or longer than a day is duration and not supported
+                mysql_lib::Value::Time(false, 0, hour, minute, second, micros) => {
+                    Value::Time(Time {
+                        hour: *hour,
+                        minute: *minute,
+                        second: *second,
+                        nanosecond: *micros * 1000,
+                    })
+                }
                 _ => {
                     return Err(rorm_error::database!(
                         "Unsupported mysql value type: {:?}",
@@ -170,71 +414,131 @@ fn mysql_row_to_rorm_row(src: mysql_lib::Row) -> Result<Row> {
                 }
             };
 
+            columns.push(column_name.clone());
             values.insert(column_name, value);
         }
     }
 
-    Ok(Row { values })
+    Ok(Row { columns, values })
 }
 
-fn gen_create_table(info: &TableInfo) -> String {
+fn gen_create_table(desc: &TableDesc) -> String {
     format!(
         "CREATE TABLE IF NOT EXISTS {table_name} ({cols})",
-        table_name = info.name,
-        cols = gen_cols(info.columns).join(", ")
+        table_name = desc.name,
+        cols = desc
+            .columns
+            .iter()
+            .map(gen_col)
+            .collect::<Vec<_>>()
+            .join(", ")
     )
 }
 
-fn gen_cols(infos: &[ColumnInfo]) -> Vec<String> {
-    let mut cols = vec![];
-
-    for info in infos {
-        if let Some(ref_info) = info.flatten_ref {
-            cols.extend(gen_cols(ref_info.columns));
+fn gen_col(col: &ColumnDesc) -> String {
+    format!(
+        "{name} {ty} {prim_key} {auto_incr} {not_null} {default} {unique}",
+        name = col.name,
+        ty = column_type_to_mysql_type(&col.ty),
+        prim_key = if col.is_primary_key {
+            "PRIMARY KEY"
         } else {
-            let col = format!(
-                "{name} {ty} {prim_key} {auto_incr} {not_null} {default} {unique}",
-                name = info.name,
-                ty = column_type_to_mysql_type(&info.ty),
-                prim_key = if info.is_primary_key {
-                    "PRIMARY KEY"
-                } else {
-                    ""
-                },
-                auto_incr = if info.is_auto_increment {
-                    "AUTO_INCREMENT"
-                } else {
-                    ""
-                },
-                not_null = if info.is_not_null { "NOT NULL" } else { "" },
-                default = info
-                    .default
-                    .map(|def| format!("DEFAULT {}", def))
-                    .unwrap_or("".into()),
-                unique = if info.is_unique { "UNIQUE" } else { "" },
-            );
-            cols.push(col);
-        }
-    }
-
-    cols
+            ""
+        },
+        auto_incr = if col.is_auto_increment {
+            "AUTO_INCREMENT"
+        } else {
+            ""
+        },
+        not_null = if col.is_not_null { "NOT NULL" } else { "" },
+        default = col
+            .default
+            .as_ref()
+            .map(|def| format!("DEFAULT {}", def))
+            .unwrap_or("".into()),
+        unique = if col.is_unique { "UNIQUE" } else { "" },
+    )
 }
 
-fn gen_create_index(table_name: &str, index_info: &IndexInfo) -> String {
-    let cols = index_info
-        .keys
-        .iter()
-        .map(|k| format!("{}", k.column_name))
-        .collect::<Vec<_>>();
-
+fn gen_create_index(table_name: &str, index: &IndexDesc) -> String {
     format!(
-        "CREATE INDEX IF NOT EXISTS {index_name} ON {table_name} ({cols})",
-        index_name = index_info.name,
+        "CREATE {unique}INDEX {index_name} ON {table_name} ({cols})",
+        unique = if index.is_unique { "UNIQUE " } else { "" },
+        index_name = index.name,
         table_name = table_name,
-        cols = cols.join(", ")
+        cols = index.keys.join(", ")
     )
 }
 
+fn gen_drop_index(table_name: &str, index_name: &str) -> String {
+    format!("DROP INDEX {} ON {}", index_name, table_name)
+}
+
+fn gen_sync_table(table_name: &str, changes: &[SchemaChange]) -> Vec<String> {
+    // Old primary key is dropped before new one is added
+    let is_pk_dropped = |change: &&SchemaChange| {
+        matches!(change, SchemaChange::AlterColumn { from, to }
+            if from.is_primary_key && !to.is_primary_key)
+    };
+
+    changes
+        .iter()
+        .filter(is_pk_dropped)
+        .chain(changes.iter().filter(|change| !is_pk_dropped(change)))
+        .flat_map(|change| match change {
+            SchemaChange::AddColumn(col) => vec![format!(
+                "ALTER TABLE {} ADD COLUMN {}",
+                table_name,
+                gen_col(col)
+            )],
+            SchemaChange::AlterColumn { from, to } => {
+                let mut sqls = vec![];
+
+                // Unique index of column is named as column
+                if from.is_unique && !to.is_unique {
+                    sqls.push(gen_drop_index(table_name, &from.name));
+                }
+
+                // Primary key and unique index already exist, cannot be declared again
+                let col = ColumnDesc {
+                    is_primary_key: to.is_primary_key && !from.is_primary_key,
+                    is_unique: to.is_unique && !from.is_unique,
+                    ..to.clone()
+                };
+                sqls.push(format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    table_name,
+                    gen_col(&col)
+                ));
+
+                // Primary key is kept by modify, dropped after auto increment is removed
+                if from.is_primary_key && !to.is_primary_key {
+                    sqls.push(format!("ALTER TABLE {} DROP PRIMARY KEY", table_name));
+                }
+
+                sqls
+            }
+            SchemaChange::DropColumn(col) => {
+                vec![format!(
+                    "ALTER TABLE {} DROP COLUMN {}",
+                    table_name, col.name
+                )]
+            }
+            SchemaChange::AddIndex(idx) => vec![gen_create_index(table_name, idx)],
+            SchemaChange::AlterIndex { from, to } => vec![
+                gen_drop_index(table_name, &from.name),
+                gen_create_index(table_name, to),
+            ],
+            SchemaChange::DropIndex(idx) => vec![gen_drop_index(table_name, &idx.name)],
+        })
+        .collect()
+}
+
+/// Whether two column types map to the same mysql type
+fn is_same_type(a: &ColumnType, b: &ColumnType) -> bool {
+    column_type_to_mysql_type(a) == column_type_to_mysql_type(b)
+}
+
 fn column_type_to_mysql_type(col: &ColumnType) -> String {
     match col {
         ColumnType::Bool => "TINYINT".into(),
@@ -262,7 +566,108 @@ fn column_type_to_mysql_type(col: &ColumnType) -> String {
                 "LONGBLOB".into()
             }
         }
+        ColumnType::Date => "DATE".into(),
+        ColumnType::Time => "TIME(6)".into(),
+        ColumnType::DateTime => "DATETIME(6)".into(),
+        ColumnType::Timestamp => "DATETIME(6)".into(),
+        ColumnType::Decimal(precision, scale) => format!("DECIMAL({},{})", precision, scale),
+        ColumnType::Uuid => "BINARY(16)".into(),
+        ColumnType::UuidText => "CHAR(36)".into(),
+        ColumnType::Json => "JSON".into(),
+        ColumnType::Enum(variants) => format!(
+            "ENUM({})",
+            variants
+                .iter()
+                .map(|v| format!("'{}'", v.replace('\'', "''")))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    }
+}
+
+/// Convert column type of information_schema (e.g. `int(10) unsigned`) to column type
+fn mysql_type_to_column_type(ty: &str) -> ColumnType {
+    // Variants are case sensitive, so they are parsed from the original type
+    let lower = ty.to_lowercase();
+    if lower.starts_with("enum(") && ty.ends_with(')') {
+        return ColumnType::Enum(parse_enum_variants(&ty[5..ty.len() - 1]).into());
+    }
+
+    let ty = lower;
+    let is_unsigned = ty.contains("unsigned");
+    let base = ty.split(['(', ' ']).next().unwrap_or_default();
+    let args = ty
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(args, _)| {
+            args.split(',')
+                .filter_map(|n| n.trim().parse::<usize>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let length = args.first().copied();
+
+    match (base, is_unsigned) {
+        ("tinyint", _) if length == Some(1) => ColumnType::Bool,
+        ("binary", _) if length == Some(16) => ColumnType::Uuid,
+        ("char", _) if length == Some(36) => ColumnType::UuidText,
+        ("bool" | "boolean", _) => ColumnType::Bool,
+        ("tinyint", false) => ColumnType::I8,
+        ("tinyint", true) => ColumnType::U8,
+        ("smallint", false) => ColumnType::I16,
+        ("smallint", true) => ColumnType::U16,
+        ("mediumint" | "int" | "integer", false) => ColumnType::I32,
+        ("mediumint" | "int" | "integer", true) => ColumnType::U32,
+        ("bigint", false) => ColumnType::I64,
+        ("bigint", true) => ColumnType::U64,
+        ("float", _) => ColumnType::F32,
+        ("double" | "real", _) => ColumnType::F64,
+        ("char" | "varchar", _) => ColumnType::Str(length.unwrap_or(65535)),
+        ("tinytext", _) => ColumnType::Str(255),
+        ("text", _) => ColumnType::Str(65535),
+        ("mediumtext", _) => ColumnType::Str(16777215),
+        ("longtext", _) => ColumnType::Str(4294967295),
+        ("binary" | "varbinary", _) => ColumnType::Bytes(length.unwrap_or(65535)),
+        ("tinyblob", _) => ColumnType::Bytes(255),
+        ("blob", _) => ColumnType::Bytes(65535),
+        ("mediumblob", _) => ColumnType::Bytes(16777215),
+        ("longblob", _) => ColumnType::Bytes(4294967295),
+        ("decimal" | "numeric", _) => {
+            ColumnType::Decimal(length.unwrap_or(10), args.get(1).copied().unwrap_or(0))
+        }
+        ("json", _) => ColumnType::Json,
+        ("date", _) => ColumnType::Date,
+        ("time", _) => ColumnType::Time,
+        ("datetime" | "timestamp", _) => ColumnType::DateTime,
+        _ => ColumnType::Str(65535),
+    }
+}
+
+/// Parse `'a','b''c'` to `["a", "b'c"]`
+fn parse_enum_variants(s: &str) -> Vec<Cow<'static, str>> {
+    let mut variants = vec![];
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue; // Skip separators
+        }
+
+        let mut variant = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            variant.push(c);
+        }
+        variants.push(variant.into());
     }
+
+    variants
 }
 
 impl From<Value> for mysql_lib::Value {
@@ -282,6 +687,24 @@ impl From<Value> for mysql_lib::Value {
             Value::F64(v) => Self::Double(v),
             Value::Str(v) => Self::Bytes(v.into_bytes()),
             Value::Bytes(v) => Self::Bytes(v),
+            Value::Date(v) => Self::Date(v.year as _, v.month, v.day, 0, 0, 0, 0),
+            Value::Time(v) => Self::Time(false, 0, v.hour, v.minute, v.second, v.nanosecond / 1000),
+            Value::DateTime(v) => datetime_to_mysql_value(&v),
+            Value::Timestamp(v) => datetime_to_mysql_value(&v.to_datetime()),
+            Value::Decimal(v) => Self::Bytes(v.to_string().into_bytes()),
+            Value::Uuid(v) => Self::Bytes(v.to_vec()),
         }
     }
 }
+
+fn datetime_to_mysql_value(v: &DateTime) -> mysql_lib::Value {
+    mysql_lib::Value::Date(
+        v.date.year as _,
+        v.date.month,
+        v.date.day,
+        v.time.hour,
+        v.time.minute,
+        v.time.second,
+        v.time.nanosecond / 1000,
+    )
+}
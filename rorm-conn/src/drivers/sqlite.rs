@@ -11,7 +11,12 @@ use std::{
 
 use rorm_error::Result;
 
-use crate::{ColumnInfo, ColumnType, Driver, IndexInfo, Row, TableInfo, Value};
+use crate::{
+    sync::{diff_table, split_changes},
+    with_serde_json::{json_contains, json_path},
+    ColumnDesc, ColumnType, Date, Decimal, Dialect, Driver, ForeignKeyDesc, IndexDesc, Row,
+    SchemaChange, SchemaDiff, SyncMode, TableDesc, TableInfo, Timestamp, Value,
+};
 
 #[cfg(feature = "runtime-tokio-0.2")]
 use tokio_02::task::spawn_blocking;
@@ -19,6 +24,14 @@ use tokio_02::task::spawn_blocking;
 #[cfg(feature = "runtime-tokio-1")]
 use tokio_1::task::spawn_blocking;
 
+/// Declared type of u64 columns, it has integer affinity and marks values to be read as u64.
+/// Values above i64::MAX are stored as 8 bytes big endian blob, blobs are greater than integers
+/// and compared by bytes, so comparison, `ORDER BY`, `MIN` and `MAX` keep the order of u64
+const U64_TYPE: &str = "UNSIGNED BIG INT";
+
+/// Collation of decimal columns, used by comparison, `ORDER BY`, `MIN` and `MAX` of the column
+const DECIMAL_COLLATION: &str = "decimal";
+
 #[derive(Clone)]
 pub struct SqliteConnProxy {
     conn: Arc<Mutex<rusqlite::Connection>>,
@@ -32,6 +45,174 @@ impl SqliteConnProxy {
     }
 }
 
+/// Register functions of mysql missing in sqlite: `json_contains(target, candidate[, path])`,
+/// `regexp(pattern, text)` which backs `text REGEXP pattern`, `concat(str, ...)`, `char_length(str)`,
+/// functions used by rendered sql: `date_part(part, date)` of date, datetime or timestamp,
+/// `u64_number(u64)` which rejects u64 above i64::MAX in arithmetic, `u64_value(u64)` which reads
+/// u64 without declared type, and collation `decimal` of decimal columns
+pub fn register_functions(conn: &rusqlite::Connection) -> Result<()> {
+    use rusqlite::{functions::FunctionFlags, types::ValueRef, Error};
+
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    conn.create_scalar_function("json_contains", -1, flags, |ctx| {
+        if !(2..=3).contains(&ctx.len()) {
+            return Err(Error::UserFunctionError(
+                "json_contains expects 2 or 3 arguments".into(),
+            ));
+        }
+
+        // Null if any argument is null
+        let mut args = vec![];
+        for i in 0..ctx.len() {
+            let arg = match ctx.get_raw(i) {
+                ValueRef::Null => return Ok(None),
+                ValueRef::Integer(v) => v.to_string(),
+                ValueRef::Real(v) => v.to_string(),
+                ValueRef::Text(v) | ValueRef::Blob(v) => String::from_utf8_lossy(v).into(),
+            };
+            args.push(arg);
+        }
+
+        let parse = |s: &str| {
+            serde_json::from_str::<serde_json::Value>(s)
+                .map_err(|e| Error::UserFunctionError(Box::new(e)))
+        };
+        let target = parse(&args[0])?;
+        let candidate = parse(&args[1])?;
+        let target = match args.get(2) {
+            Some(path) => {
+                match json_path(&target, path).map_err(|e| Error::UserFunctionError(Box::new(e)))? {
+                    Some(target) => target,
+                    None => return Ok(None),
+                }
+            }
+            None => &target,
+        };
+
+        Ok(Some(json_contains(target, &candidate)))
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register json_contains error: {}", e))?;
+
+    // Pattern is usually same for all rows of a query, so the last compiled one is cached
+    let mut cache: Option<(String, regex::Regex)> = None;
+    conn.create_scalar_function("regexp", 2, flags, move |ctx| {
+        let (pattern, text) = match (ctx.get_raw(0), ctx.get_raw(1)) {
+            (ValueRef::Null, _) | (_, ValueRef::Null) => return Ok(None),
+            (pattern, text) => (
+                pattern
+                    .as_str()
+                    .map_err(|e| Error::UserFunctionError(Box::new(e)))?,
+                match text {
+                    ValueRef::Integer(v) => v.to_string(),
+                    ValueRef::Real(v) => v.to_string(),
+                    ValueRef::Text(v) | ValueRef::Blob(v) => String::from_utf8_lossy(v).into(),
+                    ValueRef::Null => unreachable!(),
+                },
+            ),
+        };
+
+        let re = match &cache {
+            Some((cached, re)) if cached == pattern => re,
+            _ => {
+                let re = regex::Regex::new(pattern)
+                    .map_err(|e| Error::UserFunctionError(Box::new(e)))?;
+                &cache.insert((pattern.to_string(), re)).1
+            }
+        };
+
+        Ok(Some(re.is_match(&text)))
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register regexp error: {}", e))?;
+
+    // Same as mysql, null if any argument is null, `||` is `OR` in mysql so it isn't portable
+    conn.create_scalar_function("concat", -1, flags, |ctx| {
+        let mut s = String::new();
+        for i in 0..ctx.len() {
+            match ctx.get_raw(i) {
+                ValueRef::Null => return Ok(None),
+                ValueRef::Integer(v) => s.push_str(&v.to_string()),
+                ValueRef::Real(v) => s.push_str(&v.to_string()),
+                ValueRef::Text(v) | ValueRef::Blob(v) => s.push_str(&String::from_utf8_lossy(v)),
+            }
+        }
+
+        Ok(Some(s))
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register concat error: {}", e))?;
+
+    conn.create_scalar_function("char_length", 1, flags, |ctx| {
+        Ok(match ctx.get_raw(0) {
+            ValueRef::Null => None,
+            ValueRef::Integer(v) => Some(v.to_string().len() as i64),
+            ValueRef::Real(v) => Some(v.to_string().len() as i64),
+            ValueRef::Text(v) => Some(String::from_utf8_lossy(v).chars().count() as i64),
+            ValueRef::Blob(v) => Some(v.len() as i64),
+        })
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register char_length error: {}", e))?;
+
+    // Part of date, datetime stored as `YYYY-MM-DD[ HH:MM:SS]` or timestamp stored as unix
+    // seconds, e.g. `date_part('year', col)`, null if value is not a date like mysql
+    conn.create_scalar_function("date_part", 2, flags, |ctx| {
+        let part = ctx.get::<String>(0)?;
+        let date = match ctx.get_raw(1) {
+            ValueRef::Text(v) => std::str::from_utf8(v)
+                .ok()
+                .and_then(|s| s.get(..10))
+                .and_then(|s| s.parse::<Date>().ok()),
+            ValueRef::Integer(secs) => Some(Timestamp { secs, nanos: 0 }.to_datetime().date),
+            _ => None,
+        };
+
+        let date = match date {
+            Some(date) => date,
+            None => return Ok(rusqlite::types::Value::Null),
+        };
+        Ok(match part.as_str() {
+            "date" => rusqlite::types::Value::Text(date.to_string()),
+            "year" => rusqlite::types::Value::Integer(date.year as i64),
+            "month" => rusqlite::types::Value::Integer(date.month as i64),
+            "day" => rusqlite::types::Value::Integer(date.day as i64),
+            _ => {
+                return Err(Error::UserFunctionError(
+                    format!("Invalid date part: {}", part).into(),
+                ))
+            }
+        })
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register date_part error: {}", e))?;
+
+    // Blob of u64 above i64::MAX can't be used in arithmetic, e.g. `SUM`, see U64_TYPE
+    conn.create_scalar_function("u64_number", 1, flags, |ctx| match ctx.get_raw(0) {
+        ValueRef::Blob(v) if v.len() == 8 => Err(Error::UserFunctionError(
+            "u64 above i64::MAX can't be used in arithmetic of sqlite".into(),
+        )),
+        v => Ok(rusqlite::types::Value::from(v)),
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register u64_number error: {}", e))?;
+
+    // Result of expression has no declared type, e.g. `MAX(col)`, so blob of u64 is converted to
+    // text of the number, see U64_TYPE
+    conn.create_scalar_function("u64_value", 1, flags, |ctx| match ctx.get_raw(0) {
+        ValueRef::Blob(v) if v.len() == 8 => Ok(rusqlite::types::Value::Text(
+            u64::from_be_bytes(v.try_into().unwrap()).to_string(),
+        )),
+        v => Ok(rusqlite::types::Value::from(v)),
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register u64_value error: {}", e))?;
+
+    // Decimal is stored as text, compared by value instead of characters, e.g. `9.00 < 10.00`
+    conn.create_collation(DECIMAL_COLLATION, |a, b| {
+        match (a.parse::<Decimal>(), b.parse::<Decimal>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        }
+    })
+    .map_err(|e| rorm_error::connection!("Sqlite register decimal collation error: {}", e))?;
+
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl Driver for SqliteConnProxy {
     async fn execute_many(&self, pairs: Vec<(String, Vec<Vec<Value>>)>) -> Result<Vec<u64>> {
@@ -91,13 +272,23 @@ impl Driver for SqliteConnProxy {
                 rorm_error::database!("Prepare query many error: {}, sql: `{}`", e, sql_string)
             })?;
 
+            // Integers of u64 columns are stored as bits of i64
+            let is_u64_columns = stmt
+                .columns()
+                .iter()
+                .map(|col| col.decl_type() == Some(U64_TYPE))
+                .collect::<Vec<_>>();
+
             log::trace!("Query many {:?}", params);
             let mut sql_rows = stmt
                 .query(&rorm_param_to_rusqlite_param(&params)[..])
                 .map_err(|e| rorm_error::database!("Query error: {}", e))?;
             let mut rows = Vec::<Row>::new();
-            while let Ok(Some(row)) = sql_rows.next() {
-                let row = rusqlite_row_to_rorm_row(row)?;
+            while let Some(row) = sql_rows
+                .next()
+                .map_err(|e| rorm_error::database!("Query error: {}", e))?
+            {
+                let row = rusqlite_row_to_rorm_row(row, &is_u64_columns)?;
                 log::trace!("Append row: {:?}", row);
                 rows.push(row);
             }
@@ -112,11 +303,12 @@ impl Driver for SqliteConnProxy {
 
     async fn init_table(&self, info: &TableInfo) -> Result<()> {
         // Generate sql
-        let table_sql = gen_create_table(info);
-        let index_sqls = info
+        let desc = TableDesc::from(info);
+        let table_sql = gen_create_table(&desc);
+        let index_sqls = desc
             .indexes
             .iter()
-            .map(|idx| gen_create_index(info.name, idx))
+            .map(|idx| gen_create_index(&desc.name, idx))
             .collect::<Vec<_>>();
 
         // Execute sql
@@ -146,19 +338,258 @@ impl Driver for SqliteConnProxy {
 
         Ok(())
     }
+
+    async fn list_tables(&self) -> Result<Vec<String>> {
+        let rows = self
+            .query_many(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+                vec![],
+            )
+            .await?;
+
+        rows.iter().map(|row| row.get::<String>("name")).collect()
+    }
+
+    async fn describe_table(&self, name: &str) -> Result<Option<TableDesc>> {
+        // Get create sql, use for detect autoincrement
+        let table_sql = match self
+            .query_many(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+                vec![Value::Str(name.into())],
+            )
+            .await?
+            .into_iter()
+            .next()
+        {
+            Some(row) => row.get::<Option<String>>("sql")?.unwrap_or_default(),
+            None => return Ok(None),
+        };
+        let has_auto_increment = table_sql.to_uppercase().contains("AUTOINCREMENT");
+
+        // Get columns
+        let mut columns = vec![];
+        let rows = self
+            .query_many(
+                "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid",
+                vec![Value::Str(name.into())],
+            )
+            .await?;
+        for row in rows {
+            let is_primary_key = row.get::<i64>("pk")? > 0;
+            columns.push(ColumnDesc {
+                name: row.get("name")?,
+                ty: sqlite_type_to_column_type(&row.get::<String>("type")?),
+                is_primary_key,
+                is_not_null: row.get::<i64>("notnull")? != 0,
+                is_auto_increment: is_primary_key && has_auto_increment,
+                default: row.get("dflt_value")?,
+                is_unique: false,
+            });
+        }
+
+        // Get indexes, unique constraint of single column is treated as column attribute
+        let mut indexes = vec![];
+        let rows = self
+            .query_many(
+                "SELECT name, \"unique\", origin FROM pragma_index_list(?) ORDER BY name",
+                vec![Value::Str(name.into())],
+            )
+            .await?;
+        for row in rows {
+            let index_name = row.get::<String>("name")?;
+            let is_unique = row.get::<i64>("unique")? != 0;
+            let origin = row.get::<String>("origin")?;
+            if origin == "pk" {
+                continue;
+            }
+
+            let keys = self
+                .query_many(
+                    "SELECT name FROM pragma_index_info(?) ORDER BY seqno",
+                    vec![Value::Str(index_name.clone())],
+                )
+                .await?
+                .iter()
+                .map(|row| row.get::<String>("name"))
+                .collect::<Result<Vec<_>>>()?;
+
+            if origin == "u" && keys.len() == 1 {
+                if let Some(col) = columns.iter_mut().find(|col| col.name == keys[0]) {
+                    col.is_unique = true;
+                }
+            } else {
+                indexes.push(IndexDesc {
+                    name: index_name,
+                    keys,
+                    is_unique,
+                });
+            }
+        }
+
+        Ok(Some(TableDesc {
+            name: name.into(),
+            columns,
+            indexes,
+        }))
+    }
+
+    async fn list_foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKeyDesc>> {
+        let rows = self
+            .query_many(
+                "SELECT id, \"table\" AS ref_table, \"from\" AS col, \"to\" AS ref_col \
+                 FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+                vec![Value::Str(table_name.into())],
+            )
+            .await?;
+
+        let mut ids = Vec::<i64>::new();
+        let mut foreign_keys = Vec::<ForeignKeyDesc>::new();
+        for row in rows {
+            let id = row.get::<i64>("id")?;
+            let col = row.get::<String>("col")?;
+            let ref_col = row.get::<Option<String>>("ref_col")?.unwrap_or_default();
+
+            match foreign_keys.last_mut() {
+                Some(fk) if ids.last() == Some(&id) => {
+                    fk.columns.push(col);
+                    fk.ref_columns.push(ref_col);
+                }
+                _ => {
+                    ids.push(id);
+                    foreign_keys.push(ForeignKeyDesc {
+                        columns: vec![col],
+                        ref_table: row.get("ref_table")?,
+                        ref_columns: vec![ref_col],
+                    });
+                }
+            }
+        }
+
+        // Referenced columns are primary keys if omitted, e.g. `REFERENCES user`
+        for fk in foreign_keys.iter_mut() {
+            if fk.ref_columns.iter().any(|col| col.is_empty()) {
+                if let Some(desc) = self.describe_table(&fk.ref_table).await? {
+                    fk.ref_columns = desc
+                        .columns
+                        .into_iter()
+                        .filter(|col| col.is_primary_key)
+                        .map(|col| col.name)
+                        .collect();
+                }
+            }
+        }
+
+        Ok(foreign_keys)
+    }
+
+    async fn sync_table(&self, info: &TableInfo, mode: SyncMode) -> Result<SchemaDiff> {
+        self.init_table(info).await?;
+
+        let expected = storage_desc(&TableDesc::from(info));
+        let actual = self
+            .describe_table(info.name)
+            .await?
+            .ok_or(rorm_error::database!("Table `{}` not found", info.name))?;
+
+        // Diff table
+        let changes = diff_table(&expected, &actual, is_same_type);
+        let diff = split_changes(changes, mode);
+        for change in &diff.pending {
+            log::warn!("Table `{}` has pending change: {:?}", info.name, change);
+        }
+
+        // Apply changes
+        let sqls = gen_sync_table(&expected, &actual, &diff.applied);
+        if !sqls.is_empty() {
+            self.execute_many(sqls.into_iter().map(|sql| (sql, vec![vec![]])).collect())
+                .await?;
+        }
+
+        Ok(diff)
+    }
+
+    async fn drop_table(&self, name: &str, if_exists: bool) -> Result<()> {
+        let sql = format!(
+            "DROP TABLE {if_exists}{name}",
+            if_exists = if if_exists { "IF EXISTS " } else { "" },
+            name = name
+        );
+        self.execute_many(vec![(sql, vec![vec![]])]).await?;
+
+        Ok(())
+    }
+
+    async fn truncate_table(&self, name: &str) -> Result<()> {
+        let mut pairs = vec![(format!("DELETE FROM {}", name), vec![vec![]])];
+
+        // Sequence table is created with the first AUTOINCREMENT table
+        let has_sequence = !self
+            .query_many(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_sequence'",
+                vec![],
+            )
+            .await?
+            .is_empty();
+        if has_sequence {
+            pairs.push((
+                "DELETE FROM sqlite_sequence WHERE name = ?".into(),
+                vec![vec![Value::Str(name.into())]],
+            ));
+        }
+        self.execute_many(pairs).await?;
+
+        Ok(())
+    }
+
+    async fn rename_table(&self, from: &str, to: &str) -> Result<()> {
+        let sql = format!("ALTER TABLE {} RENAME TO {}", from, to);
+        self.execute_many(vec![(sql, vec![vec![]])]).await?;
+
+        Ok(())
+    }
+
+    async fn diff_table(&self, expected: &TableDesc) -> Result<Vec<SchemaChange>> {
+        let actual = self
+            .describe_table(&expected.name)
+            .await?
+            .unwrap_or_else(|| TableDesc {
+                name: expected.name.clone(),
+                columns: vec![],
+                indexes: vec![],
+            });
+
+        Ok(diff_table(&storage_desc(expected), &actual, is_same_type))
+    }
+
+    fn gen_ddl(&self, desc: &TableDesc) -> Vec<String> {
+        let mut sqls = vec![gen_create_table(desc)];
+        sqls.extend(
+            desc.indexes
+                .iter()
+                .map(|idx| gen_create_index(&desc.name, idx)),
+        );
+
+        sqls
+    }
+
+    fn dialect(&self) -> Dialect {
+        Dialect::Sqlite
+    }
 }
 
 fn rorm_param_to_rusqlite_param(params: &Vec<Value>) -> Vec<&'_ dyn rusqlite::ToSql> {
     params.iter().map(|v| v as &dyn rusqlite::ToSql).collect()
 }
 
-fn rusqlite_row_to_rorm_row<'s>(src: &rusqlite::Row<'s>) -> Result<Row> {
+fn rusqlite_row_to_rorm_row<'s>(src: &rusqlite::Row<'s>, is_u64_columns: &[bool]) -> Result<Row> {
     use rusqlite::types::ValueRef;
 
     let stmt = src.as_ref();
 
+    let mut columns = vec![];
     let mut values = HashMap::new();
-    for i in 0..stmt.column_count() {
+    // Same length as columns of statement
+    for (i, &is_u64) in is_u64_columns.iter().enumerate() {
         let column_name = stmt
             .column_name(i)
             .map_err(|e| rorm_error::database!("Get column name error: {}", e))?
@@ -167,6 +598,11 @@ fn rusqlite_row_to_rorm_row<'s>(src: &rusqlite::Row<'s>) -> Result<Row> {
         if let Ok(v) = src.get_ref(i) {
             let value = match v {
                 ValueRef::Null => Value::Null,
+                // Negative values are bits of u64 stored by old versions
+                ValueRef::Integer(v) if is_u64 => Value::U64(v as u64),
+                ValueRef::Blob(v) if is_u64 && v.len() == 8 => {
+                    Value::U64(u64::from_be_bytes(v.try_into().unwrap()))
+                }
                 ValueRef::Integer(v) => Value::I64(v),
                 ValueRef::Real(v) => Value::F64(v),
                 ValueRef::Text(v) => {
@@ -174,13 +610,14 @@ fn rusqlite_row_to_rorm_row<'s>(src: &rusqlite::Row<'s>) -> Result<Row> {
                 }
                 ValueRef::Blob(v) => Value::Bytes(v.to_vec()),
             };
+            columns.push(column_name.clone());
             values.insert(column_name, value);
         } else {
             break;
         }
     }
 
-    Ok(Row { values })
+    Ok(Row { columns, values })
 }
 
 impl rusqlite::ToSql for Value {
@@ -194,72 +631,242 @@ impl rusqlite::ToSql for Value {
             Value::I16(v) => <i16 as rusqlite::ToSql>::to_sql(v),
             Value::U32(v) => <u32 as rusqlite::ToSql>::to_sql(v),
             Value::I32(v) => <i32 as rusqlite::ToSql>::to_sql(v),
-            Value::U64(v) => <u64 as rusqlite::ToSql>::to_sql(v),
+            // Order is kept, see U64_TYPE
+            Value::U64(v) => match i64::try_from(*v) {
+                Ok(v) => Ok(v.into()),
+                Err(_) => Ok(v.to_be_bytes().to_vec().into()),
+            },
             Value::I64(v) => <i64 as rusqlite::ToSql>::to_sql(v),
             Value::F32(v) => <f32 as rusqlite::ToSql>::to_sql(v),
             Value::F64(v) => <f64 as rusqlite::ToSql>::to_sql(v),
             Value::Str(v) => <String as rusqlite::ToSql>::to_sql(v),
             Value::Bytes(v) => <Vec<u8> as rusqlite::ToSql>::to_sql(v),
+            Value::Date(v) => Ok(v.to_string().into()),
+            Value::Time(v) => Ok(v.to_string().into()),
+            Value::DateTime(v) => Ok(v.to_string().into()),
+            Value::Timestamp(v) => <i64 as rusqlite::ToSql>::to_sql(&v.secs),
+            Value::Decimal(v) => Ok(v.to_string().into()),
+            Value::Uuid(v) => Ok(v.to_vec().into()),
         }
     }
 }
 
-fn gen_create_table(info: &TableInfo) -> String {
+fn gen_create_table(desc: &TableDesc) -> String {
     format!(
         "CREATE TABLE IF NOT EXISTS {table_name} ({cols})",
-        table_name = info.name,
-        cols = gen_cols(info.columns).join(", ")
+        table_name = desc.name,
+        cols = desc
+            .columns
+            .iter()
+            .map(gen_col)
+            .collect::<Vec<_>>()
+            .join(", ")
     )
 }
 
-fn gen_cols(infos: &[ColumnInfo]) -> Vec<String> {
-    let mut cols = vec![];
-
-    for info in infos {
-        if let Some(ref_info) = info.flatten_ref {
-            cols.extend(gen_cols(ref_info.columns));
+fn gen_col(col: &ColumnDesc) -> String {
+    format!(
+        "{name} {ty} {collate} {prim_key} {auto_incr} {not_null} {default} {unique} {check}",
+        name = col.name,
+        ty = column_type_to_sqlite_type(&storage_type(col)),
+        collate = match &col.ty {
+            ColumnType::Decimal(_, _) => format!("COLLATE {}", DECIMAL_COLLATION),
+            _ => "".into(),
+        },
+        prim_key = if col.is_primary_key {
+            "PRIMARY KEY"
         } else {
-            let col = format!(
-                "{name} {ty} {prim_key} {auto_incr} {not_null} {default} {unique}",
-                name = info.name,
-                ty = column_type_to_sqlite_type(&info.ty),
-                prim_key = if info.is_primary_key {
-                    "PRIMARY KEY"
-                } else {
-                    ""
-                },
-                auto_incr = if info.is_auto_increment {
-                    "AUTOINCREMENT"
-                } else {
-                    ""
-                },
-                not_null = if info.is_not_null { "NOT NULL" } else { "" },
-                default = info
-                    .default
-                    .map(|def| format!("DEFAULT {}", def))
-                    .unwrap_or("".into()),
-                unique = if info.is_unique { "UNIQUE" } else { "" },
-            );
-            cols.push(col);
+            ""
+        },
+        auto_incr = if col.is_auto_increment {
+            "AUTOINCREMENT"
+        } else {
+            ""
+        },
+        not_null = if col.is_not_null { "NOT NULL" } else { "" },
+        default = col
+            .default
+            .as_ref()
+            .map(|def| format!("DEFAULT {}", def))
+            .unwrap_or("".into()),
+        unique = if col.is_unique { "UNIQUE" } else { "" },
+        check = match &col.ty {
+            ColumnType::Enum(variants) => format!(
+                "CHECK ({} IN ({}))",
+                col.name,
+                variants
+                    .iter()
+                    .map(|v| quote_str(v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => "".into(),
+        },
+    )
+}
+
+fn gen_create_index(table_name: &str, index: &IndexDesc) -> String {
+    format!(
+        "CREATE {unique}INDEX IF NOT EXISTS {index_name} ON {table_name} ({cols})",
+        unique = if index.is_unique { "UNIQUE " } else { "" },
+        index_name = index.name,
+        table_name = table_name,
+        cols = index.keys.join(", ")
+    )
+}
+
+fn gen_drop_index(index: &IndexDesc) -> String {
+    format!("DROP INDEX IF EXISTS {}", index.name)
+}
+
+fn gen_sync_table(
+    expected: &TableDesc,
+    actual: &TableDesc,
+    changes: &[SchemaChange],
+) -> Vec<String> {
+    // Sqlite can only add simple column in place
+    let need_rebuild = changes.iter().any(|change| match change {
+        SchemaChange::AddColumn(col) => {
+            col.is_primary_key || col.is_unique || (col.is_not_null && col.default.is_none())
         }
+        SchemaChange::AlterColumn { .. } | SchemaChange::DropColumn(_) => true,
+        _ => false,
+    });
+    if need_rebuild {
+        return gen_rebuild_table(expected, actual, changes);
     }
 
-    cols
+    changes
+        .iter()
+        .flat_map(|change| match change {
+            SchemaChange::AddColumn(col) => vec![format!(
+                "ALTER TABLE {} ADD COLUMN {}",
+                actual.name,
+                gen_col(col)
+            )],
+            SchemaChange::AddIndex(idx) => vec![gen_create_index(&actual.name, idx)],
+            SchemaChange::AlterIndex { from, to } => {
+                vec![gen_drop_index(from), gen_create_index(&actual.name, to)]
+            }
+            SchemaChange::DropIndex(idx) => vec![gen_drop_index(idx)],
+            _ => vec![],
+        })
+        .collect()
 }
 
-fn gen_create_index(table_name: &str, index_info: &IndexInfo) -> String {
-    let cols = index_info
-        .keys
+/// Create a new table with changes applied, copy rows to it, then replace the old table
+fn gen_rebuild_table(
+    expected: &TableDesc,
+    actual: &TableDesc,
+    changes: &[SchemaChange],
+) -> Vec<String> {
+    // Apply changes to actual table
+    let mut target = actual.clone();
+    for change in changes {
+        match change {
+            SchemaChange::AddColumn(col) => target.columns.push(col.clone()),
+            SchemaChange::AlterColumn { to, .. } => {
+                target.columns.retain(|col| col.name != to.name);
+                target.columns.push(to.clone());
+            }
+            SchemaChange::DropColumn(col) => target.columns.retain(|c| c.name != col.name),
+            SchemaChange::AddIndex(idx) => target.indexes.push(idx.clone()),
+            SchemaChange::AlterIndex { to, .. } => {
+                target.indexes.retain(|idx| idx.name != to.name);
+                target.indexes.push(to.clone());
+            }
+            SchemaChange::DropIndex(idx) => target.indexes.retain(|i| i.name != idx.name),
+        }
+    }
+
+    // Keep column order of table info, columns not in table info are placed at the end
+    target.columns.sort_by_key(|col| {
+        expected
+            .columns
+            .iter()
+            .position(|c| c.name == col.name)
+            .unwrap_or(usize::MAX)
+    });
+
+    // Not null column is filled with default value or zero value
+    let (cols, exprs): (Vec<_>, Vec<_>) = target
+        .columns
         .iter()
-        .map(|k| format!("{}", k.column_name))
-        .collect::<Vec<_>>();
+        .filter_map(|col| {
+            let fill = col
+                .default
+                .clone()
+                .unwrap_or_else(|| column_type_zero_value(&col.ty));
+            if actual.column(&col.name).is_some() {
+                if col.is_not_null {
+                    Some((
+                        col.name.clone(),
+                        format!("COALESCE({}, {})", col.name, fill),
+                    ))
+                } else {
+                    Some((col.name.clone(), col.name.clone()))
+                }
+            } else if col.is_not_null && col.default.is_none() {
+                Some((col.name.clone(), fill))
+            } else {
+                None
+            }
+        })
+        .unzip();
+
+    let tmp_name = format!("{}_rorm_rebuild", actual.name);
+    let mut sqls = vec![
+        gen_create_table(&TableDesc {
+            name: tmp_name.clone(),
+            columns: target.columns.clone(),
+            indexes: vec![],
+        }),
+        format!(
+            "INSERT INTO {tmp_name} ({cols}) SELECT {exprs} FROM {table_name}",
+            tmp_name = tmp_name,
+            cols = cols.join(", "),
+            exprs = exprs.join(", "),
+            table_name = actual.name,
+        ),
+        format!("DROP TABLE {}", actual.name),
+        format!("ALTER TABLE {} RENAME TO {}", tmp_name, actual.name),
+    ];
+    sqls.extend(
+        target
+            .indexes
+            .iter()
+            .map(|idx| gen_create_index(&actual.name, idx)),
+    );
 
-    format!(
-        "CREATE INDEX IF NOT EXISTS {index_name} ON {table_name} ({cols})",
-        index_name = index_info.name,
-        table_name = table_name,
-        cols = cols.join(", ")
-    )
+    sqls
+}
+
+/// Auto increment column must be `INTEGER PRIMARY KEY`, rowid never exceeds i64
+fn storage_type(col: &ColumnDesc) -> ColumnType {
+    match col.ty {
+        ColumnType::U64 if col.is_auto_increment => ColumnType::I64,
+        _ => col.ty.clone(),
+    }
+}
+
+/// Table desc with the column types actually stored
+fn storage_desc(desc: &TableDesc) -> TableDesc {
+    TableDesc {
+        columns: desc
+            .columns
+            .iter()
+            .map(|col| ColumnDesc {
+                ty: storage_type(col),
+                ..col.clone()
+            })
+            .collect(),
+        ..desc.clone()
+    }
+}
+
+/// Whether two column types map to the same sqlite type
+fn is_same_type(a: &ColumnType, b: &ColumnType) -> bool {
+    column_type_to_sqlite_type(a) == column_type_to_sqlite_type(b)
 }
 
 fn column_type_to_sqlite_type(col: &ColumnType) -> String {
@@ -272,10 +879,59 @@ fn column_type_to_sqlite_type(col: &ColumnType) -> String {
         ColumnType::I32 => "INTEGER".into(),
         ColumnType::U32 => "INTEGER".into(),
         ColumnType::I64 => "INTEGER".into(),
-        ColumnType::U64 => "INTEGER".into(),
+        ColumnType::U64 => U64_TYPE.into(),
         ColumnType::F32 => "REAL".into(),
         ColumnType::F64 => "REAL".into(),
         ColumnType::Str(_) => "TEXT".into(),
         ColumnType::Bytes(_) => "BLOB".into(),
+        ColumnType::Date => "TEXT".into(),
+        ColumnType::Time => "TEXT".into(),
+        ColumnType::DateTime => "TEXT".into(),
+        ColumnType::Timestamp => "INTEGER".into(),
+        ColumnType::Decimal(_, _) => "TEXT".into(), // Real is lossy
+        ColumnType::Uuid => "BLOB".into(),
+        ColumnType::UuidText => "TEXT".into(),
+        ColumnType::Json => "TEXT".into(), // Functions of json1 work on text
+        ColumnType::Enum(_) => "TEXT".into(), // Variants are checked by constraint of column
+    }
+}
+
+fn column_type_zero_value(col: &ColumnType) -> String {
+    let zero = match col {
+        ColumnType::Str(_) => "''",
+        ColumnType::Bytes(_) => "x''",
+        ColumnType::Date => "'1970-01-01'",
+        ColumnType::Time => "'00:00:00'",
+        ColumnType::DateTime => "'1970-01-01 00:00:00'",
+        ColumnType::Decimal(_, _) => "'0'",
+        ColumnType::Uuid => "x'00000000000000000000000000000000'",
+        ColumnType::UuidText => "'00000000-0000-0000-0000-000000000000'",
+        ColumnType::Json => "'null'",
+        ColumnType::Enum(variants) => return quote_str(variants.first().map_or("", |v| v)),
+        _ => "0",
+    };
+
+    zero.into()
+}
+
+/// Quote string as sql literal
+fn quote_str(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Convert declared type to column type, follow the rules of sqlite type affinity
+fn sqlite_type_to_column_type(ty: &str) -> ColumnType {
+    let ty = ty.to_uppercase();
+
+    if ty == U64_TYPE {
+        ColumnType::U64
+    } else if ty.contains("INT") {
+        ColumnType::I64
+    } else if ty.contains("CHAR") || ty.contains("CLOB") || ty.contains("TEXT") {
+        ColumnType::Str(65535)
+    } else if ty.contains("BLOB") || ty.is_empty() {
+        ColumnType::Bytes(65535)
+    } else {
+        ColumnType::F64
     }
 }
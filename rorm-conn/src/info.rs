@@ -1,4 +1,8 @@
+use std::borrow::Cow;
+
+/// Only serializable, deserialize into `TableDesc` instead
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TableInfo {
     pub name: &'static str,
     pub columns: &'static [ColumnInfo],
@@ -6,6 +10,7 @@ pub struct TableInfo {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColumnInfo {
     pub name: &'static str,
     pub ty: ColumnType,
@@ -17,8 +22,8 @@ pub struct ColumnInfo {
     pub flatten_ref: Option<&'static TableInfo>, // Flatten reference table info
 }
 
-// TODO: Add datetime
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColumnType {
     Bool,
     I8,
@@ -33,15 +38,26 @@ pub enum ColumnType {
     F64,
     Str(usize),   // String with max length, default is 65536
     Bytes(usize), // Binary data with max length, default is 65536
+    Date,
+    Time,
+    DateTime,              // Date and time without time zone
+    Timestamp,             // Instant in UTC
+    Decimal(usize, usize), // Precision and scale, default is (38, 10)
+    Uuid,                  // 16 bytes binary
+    UuidText,              // Hyphenated text of 36 chars
+    Json,
+    Enum(Cow<'static, [Cow<'static, str>]>), // Text restricted to the variants
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndexInfo {
     pub name: &'static str,
     pub keys: &'static [IndexKeyInfo],
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndexKeyInfo {
     pub column_name: &'static str,
 }
@@ -1,11 +1,30 @@
 mod connection;
+mod datetime;
+mod db_type;
+mod decimal;
+mod desc;
 mod drivers;
 mod info;
+mod sync;
 mod value;
+#[cfg(feature = "serde")]
+mod with_serde;
+#[cfg(feature = "serde_json")]
+mod with_serde_json;
+#[cfg(feature = "uuid")]
+mod with_uuid;
 
 use std::collections::HashMap;
 
 pub use connection::Connection;
+pub use datetime::{Date, DateTime, Time, Timestamp};
+pub use db_type::DbType;
+pub use decimal::Decimal;
+pub use desc::{ColumnDesc, ForeignKeyDesc, IndexDesc, TableDesc};
+pub use rorm_query::Dialect;
+pub use sync::{SchemaChange, SchemaDiff, SyncMode};
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
 pub use value::{FromValue, ToValue, Value};
 
 pub mod driver {
@@ -22,10 +41,22 @@ pub trait Driver: Sync + Send {
     async fn execute_many(&self, pairs: Vec<(String, Vec<Vec<Value>>)>) -> Result<Vec<u64>>; // Vec<(sql, params_list)>
     async fn query_many(&self, sql: &str, params: Vec<Value>) -> Result<Vec<Row>>;
     async fn init_table(&self, info: &TableInfo) -> Result<()>;
+    async fn list_tables(&self) -> Result<Vec<String>>;
+    async fn describe_table(&self, name: &str) -> Result<Option<TableDesc>>; // None if table not exists
+    async fn list_foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKeyDesc>>;
+    async fn sync_table(&self, info: &TableInfo, mode: SyncMode) -> Result<SchemaDiff>;
+    async fn drop_table(&self, name: &str, if_exists: bool) -> Result<()>;
+    async fn truncate_table(&self, name: &str) -> Result<()>; // Delete all rows and reset auto increment
+    async fn rename_table(&self, from: &str, to: &str) -> Result<()>;
+    async fn diff_table(&self, expected: &TableDesc) -> Result<Vec<SchemaChange>>;
+    fn gen_ddl(&self, desc: &TableDesc) -> Vec<String>; // Create table and index sqls
+    fn dialect(&self) -> Dialect;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Row {
+    pub(crate) columns: Vec<String>, // Names in selected order
     pub(crate) values: HashMap<String, Value>,
 }
 
@@ -41,4 +72,67 @@ impl Row {
             ))
         }
     }
+
+    /// Same as `get`, but numbers are converted by `as`, may overflow or lose precision
+    pub fn get_lossy<T: FromValue<Output = T>>(&self, index: &str) -> Result<T> {
+        if let Some(v) = self.values.get(index) {
+            Ok(T::from_value_lossy(v)?)
+        } else {
+            Err(rorm_error::out_of_range!(
+                "Index out of range: index: {}, values length: {}",
+                index,
+                self.values.len()
+            ))
+        }
+    }
+
+    /// Get value of column at position, columns with the same name share the last value
+    pub fn get_at<T: FromValue<Output = T>>(&self, index: usize) -> Result<T> {
+        match self.columns.get(index) {
+            Some(name) => self.get(name),
+            None => Err(rorm_error::out_of_range!(
+                "Index out of range: index: {}, columns length: {}",
+                index,
+                self.columns.len()
+            )),
+        }
+    }
+
+    pub fn value(&self, index: &str) -> Option<&Value> {
+        self.values.get(index)
+    }
+
+    /// Column names in selected order
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+/// Convert row of selected columns, e.g. grouped results,
+/// tuples are filled by position of columns
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> Result<Self>;
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($(($($ty:ident: $index:tt),+))+) => {
+        $(
+            impl<$($ty: FromValue<Output = $ty>),+> FromRow for ($($ty,)+) {
+                fn from_row(row: &Row) -> Result<Self> {
+                    Ok(($(row.get_at::<$ty>($index)?,)+))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_row_for_tuple! {
+    (A: 0)
+    (A: 0, B: 1)
+    (A: 0, B: 1, C: 2)
+    (A: 0, B: 1, C: 2, D: 3)
+    (A: 0, B: 1, C: 2, D: 3, E: 4)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
 }
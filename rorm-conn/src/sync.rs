@@ -0,0 +1,112 @@
+//! # Sync
+//!
+//! Compare the table info generated by macro with the live table, and bring the
+//! table up to date.
+
+use crate::{ColumnDesc, ColumnType, IndexDesc, TableDesc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SyncMode {
+    /// Add missing columns and indexes, other changes are only reported
+    Safe,
+    /// Apply all changes, including type changes and drops, may lose data
+    Destructive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SchemaChange {
+    AddColumn(ColumnDesc),
+    AlterColumn { from: ColumnDesc, to: ColumnDesc },
+    DropColumn(ColumnDesc),
+    AddIndex(IndexDesc),
+    AlterIndex { from: IndexDesc, to: IndexDesc },
+    DropIndex(IndexDesc),
+}
+
+impl SchemaChange {
+    /// Whether the change is only applied in `SyncMode::Destructive`
+    pub fn is_destructive(&self) -> bool {
+        !matches!(self, Self::AddColumn(_) | Self::AddIndex(_))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaDiff {
+    pub applied: Vec<SchemaChange>,
+    pub pending: Vec<SchemaChange>, // Changes found but not applied
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.pending.is_empty()
+    }
+}
+
+/// Generate changes that turn `actual` into `expected`,
+/// `same_type` checks whether two column types map to the same sql type of the dialect
+pub(crate) fn diff_table<F>(
+    expected: &TableDesc,
+    actual: &TableDesc,
+    same_type: F,
+) -> Vec<SchemaChange>
+where
+    F: Fn(&ColumnType, &ColumnType) -> bool,
+{
+    let mut changes = vec![];
+
+    // Columns
+    for col in &expected.columns {
+        match actual.column(&col.name) {
+            None => changes.push(SchemaChange::AddColumn(col.clone())),
+            Some(actual_col) => {
+                let is_same = same_type(&col.ty, &actual_col.ty)
+                    && ColumnDesc {
+                        ty: col.ty.clone(),
+                        ..actual_col.clone()
+                    } == *col;
+                if !is_same {
+                    changes.push(SchemaChange::AlterColumn {
+                        from: actual_col.clone(),
+                        to: col.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for col in &actual.columns {
+        if expected.column(&col.name).is_none() {
+            changes.push(SchemaChange::DropColumn(col.clone()));
+        }
+    }
+
+    // Indexes
+    for idx in &expected.indexes {
+        match actual.index(&idx.name) {
+            None => changes.push(SchemaChange::AddIndex(idx.clone())),
+            Some(actual_idx) if actual_idx != idx => changes.push(SchemaChange::AlterIndex {
+                from: actual_idx.clone(),
+                to: idx.clone(),
+            }),
+            _ => {}
+        }
+    }
+    for idx in &actual.indexes {
+        if expected.index(&idx.name).is_none() {
+            changes.push(SchemaChange::DropIndex(idx.clone()));
+        }
+    }
+
+    changes
+}
+
+/// Split changes to (applied, pending) by mode
+pub(crate) fn split_changes(changes: Vec<SchemaChange>, mode: SyncMode) -> SchemaDiff {
+    let (applied, pending) = changes
+        .into_iter()
+        .partition(|change| mode == SyncMode::Destructive || !change.is_destructive());
+
+    SchemaDiff { applied, pending }
+}
@@ -1,5 +1,13 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
 use rorm_error::Result;
 
+use crate::{Date, DateTime, Decimal, Dialect, Time, Timestamp};
+
 macro_rules! impl_to_value_base {
     ($ty:ty, $enum_field:ident) => {
         impl ToValue for $ty {
@@ -16,6 +24,29 @@ macro_rules! impl_from_value_integer {
             type Output = $ty;
 
             fn from_value(v: &Value) -> Result<Self::Output> {
+                let res = match v {
+                    Value::U8(v) => <$ty>::try_from(*v).ok(),
+                    Value::I8(v) => <$ty>::try_from(*v).ok(),
+                    Value::U16(v) => <$ty>::try_from(*v).ok(),
+                    Value::I16(v) => <$ty>::try_from(*v).ok(),
+                    Value::U32(v) => <$ty>::try_from(*v).ok(),
+                    Value::I32(v) => <$ty>::try_from(*v).ok(),
+                    Value::U64(v) => <$ty>::try_from(*v).ok(),
+                    Value::I64(v) => <$ty>::try_from(*v).ok(),
+                    _ => parse_value(v),
+                };
+
+                res.ok_or_else(|| {
+                    rorm_error::from_value!(
+                        "Invalid value: {:?}, output type: {}",
+                        v,
+                        stringify!($ty)
+                    )
+                })
+            }
+
+            /// Numbers are converted by `as`, may overflow or truncate
+            fn from_value_lossy(v: &Value) -> Result<Self::Output> {
                 match v {
                     Value::U8(v) => Ok(*v as $ty),
                     Value::I8(v) => Ok(*v as $ty),
@@ -25,11 +56,9 @@ macro_rules! impl_from_value_integer {
                     Value::I32(v) => Ok(*v as $ty),
                     Value::U64(v) => Ok(*v as $ty),
                     Value::I64(v) => Ok(*v as $ty),
-                    _ => Err(rorm_error::from_value!(
-                        "Invalid value: {:?}, output type: {}",
-                        v,
-                        stringify!($ty)
-                    )),
+                    Value::F32(v) => Ok(*v as $ty),
+                    Value::F64(v) => Ok(*v as $ty),
+                    _ => Self::from_value(v),
                 }
             }
         }
@@ -37,19 +66,58 @@ macro_rules! impl_from_value_integer {
 }
 
 macro_rules! impl_from_value_float {
-    ($ty:ty) => {
+    ($ty:ty, $mantissa_digits:expr) => {
         impl FromValue for $ty {
             type Output = $ty;
 
             fn from_value(v: &Value) -> Result<Self::Output> {
-                match v {
-                    Value::F32(v) => Ok(*v as $ty),
-                    Value::F64(v) => Ok(*v as $ty),
-                    _ => Err(rorm_error::from_value!(
+                // Integer is exact if it fits in mantissa
+                let from_integer = |n: i128| {
+                    if n.unsigned_abs() <= 1 << $mantissa_digits {
+                        Some(n as $ty)
+                    } else {
+                        None
+                    }
+                };
+                let res = match v {
+                    Value::F32(v) => Some(*v as $ty),
+                    // Exact if it round trips, e.g. 0.5
+                    Value::F64(v) if v.is_nan() || (*v as $ty) as f64 == *v => Some(*v as $ty),
+                    Value::F64(_) => None,
+                    Value::U8(v) => from_integer(*v as i128),
+                    Value::I8(v) => from_integer(*v as i128),
+                    Value::U16(v) => from_integer(*v as i128),
+                    Value::I16(v) => from_integer(*v as i128),
+                    Value::U32(v) => from_integer(*v as i128),
+                    Value::I32(v) => from_integer(*v as i128),
+                    Value::U64(v) => from_integer(*v as i128),
+                    Value::I64(v) => from_integer(*v as i128),
+                    _ => parse_value(v),
+                };
+
+                res.ok_or_else(|| {
+                    rorm_error::from_value!(
                         "Invalid value: {:?}, output type: {}",
                         v,
                         stringify!($ty)
-                    )),
+                    )
+                })
+            }
+
+            /// Numbers are converted by `as`, may lose precision
+            fn from_value_lossy(v: &Value) -> Result<Self::Output> {
+                match v {
+                    Value::U8(v) => Ok(*v as $ty),
+                    Value::I8(v) => Ok(*v as $ty),
+                    Value::U16(v) => Ok(*v as $ty),
+                    Value::I16(v) => Ok(*v as $ty),
+                    Value::U32(v) => Ok(*v as $ty),
+                    Value::I32(v) => Ok(*v as $ty),
+                    Value::U64(v) => Ok(*v as $ty),
+                    Value::I64(v) => Ok(*v as $ty),
+                    Value::F32(v) => Ok(*v as $ty),
+                    Value::F64(v) => Ok(*v as $ty),
+                    _ => Self::from_value(v),
                 }
             }
         }
@@ -75,7 +143,8 @@ macro_rules! impl_from_value_base {
     };
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     Null,
     Bool(bool),
@@ -91,6 +160,153 @@ pub enum Value {
     F64(f64),
     Str(String),
     Bytes(Vec<u8>),
+    Date(Date),
+    Time(Time),
+    DateTime(DateTime),
+    Timestamp(Timestamp),
+    Decimal(Decimal),
+    Uuid([u8; 16]),
+}
+
+impl Value {
+    /// Convert uuid to hyphenated text, used by uuid columns stored as text
+    pub fn into_uuid_text(self) -> Value {
+        match self {
+            Value::Uuid(v) => {
+                let hex = v.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+                Value::Str(format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..32]
+                ))
+            }
+            v => v,
+        }
+    }
+
+    /// Sql literal accepted by dialect, used by dump. Non-finite floats have no literal
+    pub fn to_sql_literal(&self, dialect: Dialect) -> Result<String> {
+        match self {
+            Value::F32(v) if !v.is_finite() => Err(rorm_error::argument!(
+                "Float {} can't be written as sql literal",
+                v
+            )),
+            Value::F64(v) if !v.is_finite() => Err(rorm_error::argument!(
+                "Float {} can't be written as sql literal",
+                v
+            )),
+            // Backslash escapes in strings of mysql unless NO_BACKSLASH_ESCAPES is set
+            Value::Str(v) if dialect == Dialect::Mysql => {
+                Ok(format!("'{}'", v.replace('\\', "\\\\").replace('\'', "''")))
+            }
+            // Sqlite stores u64 above i64::MAX as big endian blob
+            Value::U64(v) if dialect == Dialect::Sqlite && *v > i64::MAX as u64 => {
+                Ok(Value::Bytes(v.to_be_bytes().to_vec()).to_string())
+            }
+            v => Ok(v.to_string()),
+        }
+    }
+}
+
+/// Floats are compared by bits, so `NaN` equals itself and `0.0` doesn't equal `-0.0`,
+/// values of different variants are never equal, e.g. `I32(1) != I64(1)`
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::U8(a), Value::U8(b)) => a == b,
+            (Value::I8(a), Value::I8(b)) => a == b,
+            (Value::U16(a), Value::U16(b)) => a == b,
+            (Value::I16(a), Value::I16(b)) => a == b,
+            (Value::U32(a), Value::U32(b)) => a == b,
+            (Value::I32(a), Value::I32(b)) => a == b,
+            (Value::U64(a), Value::U64(b)) => a == b,
+            (Value::I64(a), Value::I64(b)) => a == b,
+            (Value::F32(a), Value::F32(b)) => a.to_bits() == b.to_bits(),
+            (Value::F64(a), Value::F64(b)) => a.to_bits() == b.to_bits(),
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::Date(a), Value::Date(b)) => a == b,
+            (Value::Time(a), Value::Time(b)) => a == b,
+            (Value::DateTime(a), Value::DateTime(b)) => a == b,
+            (Value::Timestamp(a), Value::Timestamp(b)) => a == b,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
+            (Value::Uuid(a), Value::Uuid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+/// Consistent with `PartialEq`, floats are hashed by bits
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Null => {}
+            Value::Bool(v) => v.hash(state),
+            Value::U8(v) => v.hash(state),
+            Value::I8(v) => v.hash(state),
+            Value::U16(v) => v.hash(state),
+            Value::I16(v) => v.hash(state),
+            Value::U32(v) => v.hash(state),
+            Value::I32(v) => v.hash(state),
+            Value::U64(v) => v.hash(state),
+            Value::I64(v) => v.hash(state),
+            Value::F32(v) => v.to_bits().hash(state),
+            Value::F64(v) => v.to_bits().hash(state),
+            Value::Str(v) => v.hash(state),
+            Value::Bytes(v) => v.hash(state),
+            Value::Date(v) => v.hash(state),
+            Value::Time(v) => v.hash(state),
+            Value::DateTime(v) => v.hash(state),
+            Value::Timestamp(v) => v.hash(state),
+            Value::Decimal(v) => v.hash(state),
+            Value::Uuid(v) => v.hash(state),
+        }
+    }
+}
+
+/// Literal for logs, e.g. `'it''s'`, `X'00FF'`, it is not escaped for a dialect,
+/// use `to_sql_literal` to build sql
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<String>()
+        };
+
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(v) => write!(f, "{}", *v as u8),
+            Value::U8(v) => write!(f, "{}", v),
+            Value::I8(v) => write!(f, "{}", v),
+            Value::U16(v) => write!(f, "{}", v),
+            Value::I16(v) => write!(f, "{}", v),
+            Value::U32(v) => write!(f, "{}", v),
+            Value::I32(v) => write!(f, "{}", v),
+            Value::U64(v) => write!(f, "{}", v),
+            Value::I64(v) => write!(f, "{}", v),
+            Value::F32(v) => write!(f, "{:?}", v),
+            Value::F64(v) => write!(f, "{:?}", v),
+            Value::Str(v) => write!(f, "'{}'", v.replace('\'', "''")),
+            Value::Bytes(v) => write!(f, "X'{}'", hex(v)),
+            Value::Date(v) => write!(f, "'{}'", v),
+            Value::Time(v) => write!(f, "'{}'", v),
+            Value::DateTime(v) => write!(f, "'{}'", v),
+            Value::Timestamp(v) => write!(f, "'{}'", v),
+            // Quoted so sqlite keeps it as text
+            Value::Decimal(v) => write!(f, "'{}'", v),
+            Value::Uuid(v) => write!(f, "X'{}'", hex(v)),
+        }
+    }
 }
 
 pub trait ToValue {
@@ -124,7 +340,13 @@ impl<T: ToValue> ToValue for Option<T> {
 pub trait FromValue {
     type Output;
 
+    /// Convert value, numbers are converted only if no overflow or precision loss
     fn from_value(v: &Value) -> Result<Self::Output>;
+
+    /// Convert value, numbers are converted by `as` as long as the type is numeric
+    fn from_value_lossy(v: &Value) -> Result<Self::Output> {
+        Self::from_value(v)
+    }
 }
 
 impl<T: FromValue> FromValue for Option<T> {
@@ -136,6 +358,13 @@ impl<T: FromValue> FromValue for Option<T> {
             _ => Ok(Some(T::from_value(v)?)),
         }
     }
+
+    fn from_value_lossy(v: &Value) -> Result<Self::Output> {
+        match v {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::from_value_lossy(v)?)),
+        }
+    }
 }
 
 impl FromValue for bool {
@@ -175,6 +404,15 @@ impl FromValue for String {
     }
 }
 
+/// Parse numeric text, mysql returns numbers as bytes in text protocol
+fn parse_value<T: FromStr>(v: &Value) -> Option<T> {
+    match v {
+        Value::Str(s) => s.parse().ok(),
+        Value::Bytes(b) => std::str::from_utf8(b).ok()?.parse().ok(),
+        _ => None,
+    }
+}
+
 impl_from_value_integer! {u8}
 impl_from_value_integer! {i8}
 impl_from_value_integer! {u16}
@@ -184,7 +422,7 @@ impl_from_value_integer! {i32}
 impl_from_value_integer! {u64}
 impl_from_value_integer! {i64}
 
-impl_from_value_float! {f32}
-impl_from_value_float! {f64}
+impl_from_value_float! {f32, f32::MANTISSA_DIGITS}
+impl_from_value_float! {f64, f64::MANTISSA_DIGITS}
 
 impl_from_value_base! {Vec<u8>, Bytes}
@@ -0,0 +1,42 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Date, DateTime, Decimal, Time, Timestamp};
+
+/// Serialized as the text accepted by `FromStr`, e.g. `"2022-01-02 03:04:05"`
+macro_rules! impl_serde_by_str {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(StrVisitor(PhantomData))
+            }
+        }
+    };
+}
+
+impl_serde_by_str!(Date);
+impl_serde_by_str!(Time);
+impl_serde_by_str!(DateTime);
+impl_serde_by_str!(Timestamp);
+impl_serde_by_str!(Decimal);
+
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: FromStr<Err = rorm_error::Error>> de::Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a string of {}", std::any::type_name::<T>())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
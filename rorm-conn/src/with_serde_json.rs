@@ -0,0 +1,86 @@
+use rorm_error::Result;
+use serde_json::Value as JsonValue;
+
+use crate::{db_type::impl_db_type, ColumnType, FromValue, ToValue, Value};
+
+impl ToValue for JsonValue {
+    fn to_value(&self) -> Value {
+        Value::Str(self.to_string())
+    }
+}
+
+impl FromValue for JsonValue {
+    type Output = JsonValue;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let s = String::from_value(v)?;
+
+        serde_json::from_str(&s).map_err(|e| {
+            rorm_error::from_value!(
+                "Invalid value: {:?}, output type: serde_json::Value, {}",
+                s,
+                e
+            )
+        })
+    }
+}
+
+impl_db_type! {JsonValue, ColumnType::Json}
+
+/// Whether `candidate` is contained in `target`, same as `JSON_CONTAINS` of mysql
+#[cfg(feature = "sqlite")]
+pub(crate) fn json_contains(target: &JsonValue, candidate: &JsonValue) -> bool {
+    match (target, candidate) {
+        (JsonValue::Array(target), JsonValue::Array(candidate)) => candidate
+            .iter()
+            .all(|c| target.iter().any(|t| json_contains(t, c))),
+        (JsonValue::Array(target), _) => target.iter().any(|t| json_contains(t, candidate)),
+        (JsonValue::Object(target), JsonValue::Object(candidate)) => {
+            candidate.iter().all(|(key, c)| match target.get(key) {
+                Some(t) => json_contains(t, c),
+                None => false,
+            })
+        }
+        (JsonValue::Number(target), JsonValue::Number(candidate)) => {
+            target.as_f64() == candidate.as_f64()
+        }
+        _ => target == candidate,
+    }
+}
+
+/// Get value by path such as `$.a.b[0]` or `$."key with space"`, None if not found
+#[cfg(feature = "sqlite")]
+pub(crate) fn json_path<'v>(v: &'v JsonValue, path: &str) -> Result<Option<&'v JsonValue>> {
+    let invalid = || rorm_error::argument!("Invalid json path: {:?}", path);
+
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(invalid)?;
+    let mut v = v;
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('[') {
+            let (index, r) = r.split_once(']').ok_or_else(invalid)?;
+            let index = index.trim().parse::<usize>().map_err(|_| invalid())?;
+            match v.get(index) {
+                Some(child) => v = child,
+                None => return Ok(None),
+            }
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix('.') {
+            let (key, r) = match r.strip_prefix('"') {
+                Some(r) => r.split_once('"').ok_or_else(invalid)?,
+                None => r.split_at(r.find(['.', '[']).unwrap_or(r.len())),
+            };
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            match v.get(key) {
+                Some(child) => v = child,
+                None => return Ok(None),
+            }
+            rest = r;
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(Some(v))
+}
@@ -0,0 +1,35 @@
+use rorm_error::Result;
+use uuid::Uuid;
+
+use crate::{db_type::impl_db_type, ColumnType, FromValue, ToValue, Value};
+
+impl ToValue for Uuid {
+    fn to_value(&self) -> Value {
+        Value::Uuid(*self.as_bytes())
+    }
+}
+
+/// Both binary and text columns are accepted
+impl FromValue for Uuid {
+    type Output = Uuid;
+
+    fn from_value(v: &Value) -> Result<Self::Output> {
+        let res = match v {
+            Value::Uuid(v) => Ok(Uuid::from_bytes(*v)),
+            Value::Bytes(v) if v.len() == 16 => Uuid::from_slice(v),
+            Value::Bytes(v) => Uuid::try_parse_ascii(v),
+            Value::Str(v) => Uuid::try_parse(v),
+            _ => {
+                return Err(rorm_error::from_value!(
+                    "Invalid value: {:?}, output type: Uuid",
+                    v
+                ))
+            }
+        };
+
+        res.map_err(|e| rorm_error::from_value!("Invalid value: {:?}, output type: Uuid, {}", v, e))
+    }
+}
+
+// Stored as binary, `uuid = text` of column overrides it
+impl_db_type! {Uuid, ColumnType::Uuid}
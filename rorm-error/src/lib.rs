@@ -18,6 +18,8 @@ pub enum Error {
     QueryBuilder(String),
     #[error("Argument: `{0}`")]
     Argument(String),
+    #[error("Migration: `{0}`")]
+    Migration(String),
 }
 
 #[macro_export]
@@ -59,3 +61,8 @@ macro_rules! query_builder {
 macro_rules! argument {
     ($($arg:tt)*) => { $crate::Error::Argument(format!($($arg)*)) };
 }
+
+#[macro_export]
+macro_rules! migration {
+    ($($arg:tt)*) => { $crate::Error::Migration(format!($($arg)*)) };
+}
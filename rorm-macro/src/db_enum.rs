@@ -0,0 +1,182 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::{format_ident, quote, ToTokens};
+use syn::{Data, DeriveInput, Fields};
+
+use crate::parser::{parse_rorm_attr, AttrInfo};
+
+#[derive(Debug)]
+struct EnumInfo {
+    name: String,
+    repr: Option<String>, // Integer type, stored as text if none
+    length: Option<usize>,
+    is_check: bool,
+    variants: Vec<(String, String)>, // (ident, text)
+}
+
+pub fn derive(input: DeriveInput) -> TokenStream {
+    let info = parse(&input);
+
+    generate(&info)
+}
+
+fn parse(input: &DeriveInput) -> EnumInfo {
+    let en = match &input.data {
+        Data::Enum(en) => en,
+        _ => abort!(input, "DbEnum must be an enum"),
+    };
+
+    let mut repr = None;
+    let mut length = None;
+    let mut is_check = false;
+    for attr in &input.attrs {
+        if attr.path.to_token_stream().to_string() != "rorm" {
+            continue;
+        }
+
+        for attr_info in parse_rorm_attr(attr) {
+            match attr_info {
+                AttrInfo::Repr(ty) => repr = Some(ty),
+                AttrInfo::Length(len) => length = Some(len),
+                AttrInfo::Check => is_check = true,
+                _ => abort!(attr, "Invalid enum attr field: {:?}", attr_info),
+            }
+        }
+    }
+    if is_check && repr.is_some() {
+        abort!(input, "Check is only supported by enums stored as text");
+    }
+
+    let mut variants = vec![];
+    for variant in &en.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            abort!(variant, "Variant of DbEnum must be unit");
+        }
+
+        let ident = variant.ident.to_string();
+        let mut text = ident.clone();
+        for attr in &variant.attrs {
+            if attr.path.to_token_stream().to_string() != "rorm" {
+                continue;
+            }
+
+            for attr_info in parse_rorm_attr(attr) {
+                match attr_info {
+                    AttrInfo::Rename(name) => text = name,
+                    _ => abort!(attr, "Invalid variant attr field: {:?}", attr_info),
+                }
+            }
+        }
+        if repr.is_some() && text != ident {
+            abort!(variant, "Rename is only supported by enums stored as text");
+        }
+        if variants.iter().any(|(_, t)| t == &text) {
+            abort!(variant, "Duplicate variant name '{}'", text);
+        }
+
+        variants.push((ident, text));
+    }
+    if variants.is_empty() {
+        abort!(input, "DbEnum must have variants");
+    }
+
+    EnumInfo {
+        name: input.ident.to_string(),
+        repr,
+        length,
+        is_check,
+        variants,
+    }
+}
+
+fn generate(info: &EnumInfo) -> TokenStream {
+    let name = format_ident!("{}", info.name);
+    let idents = info
+        .variants
+        .iter()
+        .map(|(ident, _)| format_ident!("{}", ident))
+        .collect::<Vec<_>>();
+    let texts = info
+        .variants
+        .iter()
+        .map(|(_, text)| text)
+        .collect::<Vec<_>>();
+
+    let (column_type_toks, to_value_toks, from_value_toks) = match &info.repr {
+        // Integer, variant is converted by discriminant
+        Some(repr) => {
+            // Variants of value and column type are both named by upper case type
+            let kind = format_ident!("{}", repr.to_uppercase());
+            let repr = format_ident!("{}", repr);
+
+            (
+                quote! { rorm::ColumnType::#kind },
+                quote! {
+                    match self {
+                        #(Self::#idents => rorm::Value::#kind(Self::#idents as #repr),)*
+                    }
+                },
+                quote! {
+                    let n = <#repr as rorm::FromValue>::from_value(v)?;
+                    #(if n == Self::#idents as #repr {
+                        return Ok(Self::#idents);
+                    })*
+
+                    Err(rorm::error::from_value!("Invalid value {} of enum {}", n, stringify!(#name)))
+                },
+            )
+        }
+
+        // Text, variant is converted by name
+        None => {
+            let column_type = if info.is_check {
+                quote! {
+                    rorm::ColumnType::Enum(std::borrow::Cow::Borrowed(&[
+                        #(std::borrow::Cow::Borrowed(#texts)),*
+                    ]))
+                }
+            } else {
+                let length = info.length.unwrap_or(65535);
+                quote! { rorm::ColumnType::Str(#length) }
+            };
+
+            (
+                column_type,
+                quote! {
+                    match self {
+                        #(Self::#idents => rorm::Value::Str(#texts.into()),)*
+                    }
+                },
+                quote! {
+                    let s = <String as rorm::FromValue>::from_value(v)?;
+                    match s.as_str() {
+                        #(#texts => Ok(Self::#idents),)*
+                        _ => Err(rorm::error::from_value!("Invalid value '{}' of enum {}", s, stringify!(#name))),
+                    }
+                },
+            )
+        }
+    };
+
+    quote! {
+        impl rorm::ToValue for #name {
+            fn to_value(&self) -> rorm::Value {
+                #to_value_toks
+            }
+        }
+
+        impl rorm::FromValue for #name {
+            type Output = Self;
+
+            fn from_value(v: &rorm::Value) -> rorm::error::Result<Self::Output> {
+                #from_value_toks
+            }
+        }
+
+        impl rorm::DbType for #name {
+            const COLUMN_TYPE: rorm::ColumnType = #column_type_toks;
+        }
+
+        impl rorm::DbEnum for #name {}
+    }
+}
@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+/// Newtype of a single field, column type and conversions are same as the inner type
+pub fn derive(input: DeriveInput) -> TokenStream {
+    let inner_ty = match &input.data {
+        Data::Struct(st) => match &st.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => abort!(input, "DbType must be a tuple struct of one field"),
+        },
+        _ => abort!(input, "DbType must be a struct, use DbEnum for enums"),
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics rorm::ToValue for #name #ty_generics #where_clause {
+            fn to_value(&self) -> rorm::Value {
+                rorm::ToValue::to_value(&self.0)
+            }
+        }
+
+        impl #impl_generics rorm::FromValue for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            fn from_value(v: &rorm::Value) -> rorm::error::Result<Self::Output> {
+                Ok(Self(<#inner_ty as rorm::FromValue>::from_value(v)?))
+            }
+
+            fn from_value_lossy(v: &rorm::Value) -> rorm::error::Result<Self::Output> {
+                Ok(Self(<#inner_ty as rorm::FromValue>::from_value_lossy(v)?))
+            }
+        }
+
+        impl #impl_generics rorm::DbType for #name #ty_generics #where_clause {
+            const COLUMN_TYPE: rorm::ColumnType = <#inner_ty as rorm::DbType>::COLUMN_TYPE;
+            const IS_NOT_NULL: bool = <#inner_ty as rorm::DbType>::IS_NOT_NULL;
+        }
+    }
+}
@@ -85,7 +85,7 @@ fn gen_model(info: &TableInfo) -> TokenStream {
         .iter()
         .filter(|col| col.relation.is_none()) // Skip relation field
         .map(|col| {
-            let name_str = &col.name;
+            let name_str = &col.column;
             let name = str_to_toks(&col.name);
 
             if col.is_serde_json {
@@ -104,6 +104,12 @@ fn gen_model(info: &TableInfo) -> TokenStream {
                             arr.extend(v.into_set_pairs());
                         }
                     }
+                } else if col.is_uuid_text {
+                    quote! {
+                        if let rorm::Set(v) = self.#name {
+                            arr.push((#name_str, v.to_value().into_uuid_text()));
+                        }
+                    }
                 } else {
                     quote! {
                         if let rorm::Set(v) = self.#name {
@@ -135,13 +141,78 @@ fn gen_model(info: &TableInfo) -> TokenStream {
             })
             .collect(),
     };
-    let convert_id_to_primary_key_toks = if primary_key_types.to_string() != "()" {
-        quote! { id as #primary_key_types }
+    // Uuid primary key is generated by client, so inserted id of database is unused
+    let uuid_primary_key = match info.primary_keys.as_slice() {
+        [key] => info
+            .columns
+            .iter()
+            .find(|col| &col.name == key && !col.is_auto_increment && is_uuid_type(&col.ty)),
+        _ => None,
+    };
+    let convert_id_to_primary_key_toks = if uuid_primary_key.is_some() {
+        // Returned by gen_primary_key
+        quote! { None }
+    } else if is_integer_type(&primary_key_types.to_string()) {
+        quote! { Some(id as #primary_key_types) }
+    } else if info.primary_keys.len() == 1 {
+        // Newtype of integer, checked by assert_integer_key_toks
+        quote! {
+            <#primary_key_types as rorm::FromValue>::from_value(&rorm::Value::U64(id)).ok()
+        }
+    } else if primary_key_types.to_string() != "()" {
+        quote! { Some(id as #primary_key_types) }
+    } else {
+        quote! { Some(()) }
+    };
+    // Other keys are rejected when compiled, they can't be converted from inserted id
+    let assert_integer_key_toks = if uuid_primary_key.is_none()
+        && info.primary_keys.len() == 1
+        && !is_integer_type(&primary_key_types.to_string())
+    {
+        quote! {
+            const _: () = {
+                const COLUMN_TYPE: &rorm::ColumnType =
+                    &<#primary_key_types as rorm::DbType>::COLUMN_TYPE;
+                assert!(
+                    matches!(
+                        COLUMN_TYPE,
+                        rorm::ColumnType::I8
+                            | rorm::ColumnType::U8
+                            | rorm::ColumnType::I16
+                            | rorm::ColumnType::U16
+                            | rorm::ColumnType::I32
+                            | rorm::ColumnType::U32
+                            | rorm::ColumnType::I64
+                            | rorm::ColumnType::U64
+                    ),
+                    "Primary key must be integer, newtype of integer or uuid"
+                );
+            };
+        }
     } else {
         quote! {}
     };
+    let gen_primary_key_toks = match uuid_primary_key {
+        Some(col) => {
+            let name = str_to_toks(&col.name);
+            quote! {
+                fn gen_primary_key(&mut self) -> Option<#primary_key_types> {
+                    let id = match self.#name {
+                        rorm::Set(id) => id,
+                        rorm::NotSet => rorm::Uuid::new_v4(),
+                    };
+                    self.#name = rorm::Set(id);
+
+                    Some(id)
+                }
+            }
+        }
+        None => quote! {},
+    };
 
     quote! {
+        #assert_integer_key_toks
+
         // Model name
         #[derive(Debug, Default)]
         pub struct #model_name {
@@ -179,9 +250,11 @@ fn gen_model(info: &TableInfo) -> TokenStream {
                 arr
             }
 
-            fn to_primary_key(id: u64) -> #primary_key_types {
+            fn to_primary_key(id: u64) -> Option<#primary_key_types> {
                 #convert_id_to_primary_key_toks
             }
+
+            #gen_primary_key_toks
         }
     }
 }
@@ -193,10 +266,10 @@ fn gen_table_info(info: &TableInfo) -> TokenStream {
         .iter()
         .filter(|col| col.relation.is_none()) // Skip relation fields
         .map(|col| {
-            let name = &col.name;
+            let name = &col.column;
             let ty = str_to_toks(&col.ty);
             let (ty_toks, is_not_null) = gen_column_type_and_is_not_null(&col);
-            let is_primary_key = info.primary_keys.contains(name);
+            let is_primary_key = info.primary_keys.contains(&col.name);
             let is_auto_increment = col.is_auto_increment;
             let default = if let Some(def) = &col.default {
                 quote! { Some(#def) }
@@ -268,7 +341,7 @@ fn gen_impl_table_from_row(info: &TableInfo) -> TokenStream {
         .columns
         .iter()
         .map(|col| {
-            let name_str = &col.name;
+            let name_str = &col.column;
             let name = str_to_toks(&col.name);
             let ty = str_to_toks(&col.ty);
 
@@ -277,7 +350,12 @@ fn gen_impl_table_from_row(info: &TableInfo) -> TokenStream {
                 let relation_struct = str_to_toks(&relation.ty);
                 let relation_model = str_to_toks(&format!("{}Model", relation.ty)); // FIXME: Model rule must be defined in one place
                 let relation_field = str_to_toks(&relation.ref_col);
-                let relation_self_col_name = &relation.self_col;
+                // Self column is a field, read by its column name
+                let relation_self_col_name = info
+                    .columns
+                    .iter()
+                    .find(|col| col.name == relation.self_col)
+                    .map_or(&relation.self_col, |col| &col.column);
                 let model_toks = quote! {
                     #relation_model {
                         #relation_field: rorm::Set(row.get(#relation_self_col_name)?),
@@ -320,6 +398,10 @@ fn gen_impl_table_from_row(info: &TableInfo) -> TokenStream {
                         quote! {
                             #name: <#ty as rorm::Entity>::from_row(conn, &row).await?,
                         }
+                    } else if col.is_lossy {
+                        quote! {
+                            #name: row.get_lossy(#name_str)?,
+                        }
                     } else {
                         quote! {
                             #name: row.get(#name_str)?,
@@ -381,41 +463,76 @@ fn gen_primary_key_type_toks(columns: &[ColumnInfo], primary_keys: &[String]) ->
     }
 }
 
-fn gen_column_type_and_is_not_null(col: &ColumnInfo) -> (TokenStream, bool) {
+fn gen_column_type_and_is_not_null(col: &ColumnInfo) -> (TokenStream, TokenStream) {
     if col.is_flatten {
         // Type is unused if column is flatten
-        return (quote! { rorm::ColumnType::Bool }, true);
+        return (quote! { rorm::ColumnType::Bool }, quote! { true });
     }
 
-    let length = col.length.unwrap_or(65535);
-
-    match col.sql_ty.replace(" ", "").as_str() {
-        "bool" => (quote! { rorm::ColumnType::Bool }, true),
-        "i8" => (quote! { rorm::ColumnType::I8 }, true),
-        "u8" => (quote! { rorm::ColumnType::U8 }, true),
-        "i16" => (quote! { rorm::ColumnType::I16 }, true),
-        "u16" => (quote! { rorm::ColumnType::U16 }, true),
-        "i32" => (quote! { rorm::ColumnType::I32 }, true),
-        "u32" => (quote! { rorm::ColumnType::U32 }, true),
-        "i64" => (quote! { rorm::ColumnType::I64 }, true),
-        "u64" => (quote! { rorm::ColumnType::U64 }, true),
-        "f32" => (quote! { rorm::ColumnType::F32 }, true),
-        "f64" => (quote! { rorm::ColumnType::F64 }, true),
-        "String" => (quote! { rorm::ColumnType::Str(#length) }, true),
-        "Vec<u8>" => (quote! { rorm::ColumnType::Bytes(#length) }, true),
-        "Option<bool>" => (quote! { rorm::ColumnType::Bool }, false),
-        "Option<i8>" => (quote! { rorm::ColumnType::I8 }, false),
-        "Option<u8>" => (quote! { rorm::ColumnType::U8 }, false),
-        "Option<i16>" => (quote! { rorm::ColumnType::I16 }, false),
-        "Option<u16>" => (quote! { rorm::ColumnType::U16 }, false),
-        "Option<i32>" => (quote! { rorm::ColumnType::I32 }, false),
-        "Option<u32>" => (quote! { rorm::ColumnType::U32 }, false),
-        "Option<i64>" => (quote! { rorm::ColumnType::I64 }, false),
-        "Option<u64>" => (quote! { rorm::ColumnType::U64 }, false),
-        "Option<f32>" => (quote! { rorm::ColumnType::F32 }, false),
-        "Option<f64>" => (quote! { rorm::ColumnType::F64 }, false),
-        "Option<String>" => (quote! { rorm::ColumnType::Str(#length) }, false),
-        "Option<Vec<u8>>" => (quote! { rorm::ColumnType::Bytes(#length) }, false),
-        _ => panic!("Unsupported column type '{}', name: '{}'", col.ty, col.name),
+    if col.is_json_column {
+        if col.length.is_some() {
+            panic!("Json column has no length, name: '{}'", col.name);
+        }
+        return (quote! { rorm::ColumnType::Json }, quote! { true });
+    }
+
+    if col.is_serde_json {
+        // Json is serialized to string
+        let length = col.length.unwrap_or(65535);
+        return (quote! { rorm::ColumnType::Str(#length) }, quote! { true });
+    }
+
+    // Column type is defined by DbType, then overridden by attributes
+    let ty = str_to_toks(&col.sql_ty);
+    let column_type = quote! { <#ty as rorm::DbType>::COLUMN_TYPE };
+    let mut arms = vec![];
+    if let Some(length) = col.length {
+        arms.push(quote! { rorm::ColumnType::Str(_) => rorm::ColumnType::Str(#length), });
+        arms.push(quote! { rorm::ColumnType::Bytes(_) => rorm::ColumnType::Bytes(#length), });
     }
+    if col.precision.is_some() || col.scale.is_some() {
+        let precision = col.precision.unwrap_or(38);
+        let scale = col.scale.unwrap_or(10);
+        if scale > precision {
+            panic!(
+                "Scale {} is greater than precision {}, name: '{}'",
+                scale, precision, col.name
+            );
+        }
+        arms.push(quote! {
+            rorm::ColumnType::Decimal(_, _) => rorm::ColumnType::Decimal(#precision, #scale),
+        });
+    }
+    if col.is_uuid_text {
+        arms.push(quote! { rorm::ColumnType::Uuid => rorm::ColumnType::UuidText, });
+    }
+
+    // Matched by a const reference, column type can't be dropped in const
+    let ty_toks = if arms.is_empty() {
+        column_type
+    } else {
+        quote! {
+            {
+                const COLUMN_TYPE: &rorm::ColumnType = &#column_type;
+                match COLUMN_TYPE {
+                    #(#arms)*
+                    _ => #column_type,
+                }
+            }
+        }
+    };
+
+    (ty_toks, quote! { <#ty as rorm::DbType>::IS_NOT_NULL })
+}
+
+/// `Uuid`, `uuid::Uuid` or `rorm::Uuid`
+fn is_uuid_type(ty: &str) -> bool {
+    ty.replace(' ', "").rsplit("::").next() == Some("Uuid")
+}
+
+fn is_integer_type(ty: &str) -> bool {
+    matches!(
+        ty,
+        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "isize" | "usize"
+    )
 }
@@ -1,3 +1,5 @@
+mod db_enum;
+mod db_type;
 mod generator;
 mod parser;
 
@@ -16,3 +18,21 @@ pub fn derive_entity(item: TokenStream) -> TokenStream {
 
     stream.into()
 }
+
+#[proc_macro_derive(DbEnum, attributes(rorm))]
+#[proc_macro_error]
+pub fn derive_db_enum(item: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(item);
+    let stream = db_enum::derive(input);
+
+    stream.into()
+}
+
+#[proc_macro_derive(DbType)]
+#[proc_macro_error]
+pub fn derive_db_type(item: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(item);
+    let stream = db_type::derive(input);
+
+    stream.into()
+}
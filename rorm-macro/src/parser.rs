@@ -14,15 +14,21 @@ pub struct RelationInfo {
 #[derive(Debug, Clone)]
 pub struct ColumnInfo {
     pub name: String,   // Column rust name
+    pub column: String, // Column sql name, default it's same with name
     pub ty: String,     // Column rust type
     pub sql_ty: String, // User specific type, use for generate sql type, default it's same with ty
     pub length: Option<usize>,
+    pub precision: Option<usize>, // Total digits of decimal
+    pub scale: Option<usize>,     // Fraction digits of decimal
     pub is_auto_increment: bool,
     pub default: Option<String>, // Sql literal
     pub is_unique: bool,
     pub relation: Option<RelationInfo>,
-    pub is_serde_json: bool, // Default is false
-    pub is_flatten: bool,    // Default is false
+    pub is_serde_json: bool,  // Default is false
+    pub is_json_column: bool, // Serde json stored as json column instead of string, default is false
+    pub is_flatten: bool,     // Default is false
+    pub is_uuid_text: bool,   // Store uuid as text, default is false
+    pub is_lossy: bool,       // Convert numbers by `as` when read, default is false
 }
 
 #[derive(Debug)]
@@ -36,10 +42,12 @@ pub struct TableInfo {
 }
 
 #[derive(Debug)]
-enum AttrInfo {
+pub enum AttrInfo {
     TableName(String),
     PrimaryKey,
     Length(usize),
+    Precision(usize),
+    Scale(usize),
     AutoIncrement,
     Type(String),
     Index(Vec<String>),
@@ -47,7 +55,14 @@ enum AttrInfo {
     Unique,
     Relation((String, String)), // (self_col, ref_col)
     SerdeJson,
+    JsonColumn,
     Flatten,
+    UuidText(bool),
+    Repr(String), // Integer type of enum
+    Check,
+    Rename(String),
+    Lossy,
+    Column(String),
 }
 
 pub fn parse(input: DeriveInput) -> TableInfo {
@@ -89,6 +104,15 @@ pub fn parse(input: DeriveInput) -> TableInfo {
     // Parse columns
     let (columns, primary_keys) = parse_columns(st);
 
+    // Index key of field is renamed to its column, e.g. field `type_` of column `type`
+    for keys in indexes.iter_mut() {
+        for key in keys.iter_mut() {
+            if let Some(col) = columns.iter().find(|col| &col.name == key) {
+                *key = col.column.clone();
+            }
+        }
+    }
+
     TableInfo {
         struct_name,
         table_name,
@@ -111,14 +135,20 @@ fn parse_columns(st: &DataStruct) -> (Vec<ColumnInfo>, Vec<String>) {
             abort!(field, "Field must be named");
         };
         let ty = field.ty.to_token_stream().to_string();
+        let mut column = name.clone();
         let mut sql_ty = ty.clone();
         let mut length = None;
+        let mut precision = None;
+        let mut scale = None;
         let mut is_auto_increment = false;
         let mut default = Option::<String>::None;
         let mut is_unique = false;
         let mut relation = Option::<RelationInfo>::None;
         let mut is_serde_json = false;
+        let mut is_json_column = false;
         let mut is_flatten = false;
+        let mut is_uuid_text = false;
+        let mut is_lossy = false;
 
         // Parse attr
         for attr in &field.attrs {
@@ -134,6 +164,8 @@ fn parse_columns(st: &DataStruct) -> (Vec<ColumnInfo>, Vec<String>) {
                 match attr_info {
                     AttrInfo::PrimaryKey => primary_keys.push(name.clone()),
                     AttrInfo::Length(len) => length = Some(len),
+                    AttrInfo::Precision(p) => precision = Some(p),
+                    AttrInfo::Scale(s) => scale = Some(s),
                     AttrInfo::AutoIncrement => is_auto_increment = true,
                     AttrInfo::Type(ty) => sql_ty = ty.clone(),
                     AttrInfo::Default(def) => default = Some(def),
@@ -142,7 +174,11 @@ fn parse_columns(st: &DataStruct) -> (Vec<ColumnInfo>, Vec<String>) {
                         relation = Some(parse_relation(&ty, self_col, ref_col))
                     }
                     AttrInfo::SerdeJson => is_serde_json = true,
+                    AttrInfo::JsonColumn => is_json_column = true,
                     AttrInfo::Flatten => is_flatten = true,
+                    AttrInfo::UuidText(is_text) => is_uuid_text = is_text,
+                    AttrInfo::Lossy => is_lossy = true,
+                    AttrInfo::Column(name) => column = name,
                     _ => abort!(attr, "Invalid column attr field: {:?}", attr_info),
                 }
             }
@@ -151,24 +187,30 @@ fn parse_columns(st: &DataStruct) -> (Vec<ColumnInfo>, Vec<String>) {
         // Append column
         columns.push(ColumnInfo {
             name,
+            column,
             ty,
             sql_ty,
             length,
+            precision,
+            scale,
             is_auto_increment,
             default,
             is_unique,
             relation,
             is_serde_json,
+            is_json_column,
             is_flatten,
+            is_uuid_text,
+            is_lossy,
         });
     }
 
     (columns, primary_keys)
 }
 
-fn parse_rorm_attr(attr: &Attribute) -> Vec<AttrInfo> {
+pub fn parse_rorm_attr(attr: &Attribute) -> Vec<AttrInfo> {
     const PARSE_ERR_STR: &'static str = "Parse failed, syntax is #[rorm(field [= value])]";
-    const ARG_HELP: &'static str = r#"Syntax is rorm(primary_key | auto_increment | unique | flatten | table_name = "NAME" | relation = SELF_COLUMN > REFER_COLUMN | serde = serde_json | length = NUMBER | default = (NUMBER | STR) | index = [col1, col2, ...], ...)"#;
+    const ARG_HELP: &'static str = r#"Syntax is rorm(primary_key | auto_increment | unique | flatten | check | lossy | table_name = "NAME" | column = "NAME" | rename = "NAME" | repr = (i8 | u8 | ... | u64) | relation = SELF_COLUMN > REFER_COLUMN | serde = (serde_json | json_column) | uuid = (binary | text) | length = NUMBER | precision = NUMBER | scale = NUMBER | default = (NUMBER | STR) | index = [col1, col2, ...], ...)"#;
 
     let mut attrs = Vec::<AttrInfo>::new();
 
@@ -201,6 +243,12 @@ fn parse_rorm_attr(attr: &Attribute) -> Vec<AttrInfo> {
                     // Parse flatten
                     "flatten" => attrs.push(AttrInfo::Flatten),
 
+                    // Parse check
+                    "check" => attrs.push(AttrInfo::Check),
+
+                    // Parse lossy
+                    "lossy" => attrs.push(AttrInfo::Lossy),
+
                     // Error
                     _ => abort!(expr, "Syntax error while decode path"; help = ARG_HELP),
                 }
@@ -211,10 +259,33 @@ fn parse_rorm_attr(attr: &Attribute) -> Vec<AttrInfo> {
                     // Parse table_name = "NAME"
                     "table_name" => attrs.push(AttrInfo::TableName(get_str(&assign.right))),
 
+                    // Parse column = "NAME"
+                    "column" => attrs.push(AttrInfo::Column(get_str(&assign.right))),
+
+                    // Parse rename = "NAME"
+                    "rename" => attrs.push(AttrInfo::Rename(get_str(&assign.right))),
+
+                    // Parse repr = (i8 | u8 | ... | u64)
+                    "repr" => {
+                        let ty = get_path(&assign.right);
+                        match ty.as_str() {
+                            "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" => {
+                                attrs.push(AttrInfo::Repr(ty))
+                            }
+                            _ => abort!(assign.right, "Invalid repr, expect integer type"),
+                        }
+                    }
+
                     // Parse length = NUMBER
                     "length" => attrs.push(AttrInfo::Length(get_num(&assign.right))),
 
-                    // Parse type_alias = serde_json
+                    // Parse precision = NUMBER
+                    "precision" => attrs.push(AttrInfo::Precision(get_num(&assign.right))),
+
+                    // Parse scale = NUMBER
+                    "scale" => attrs.push(AttrInfo::Scale(get_num(&assign.right))),
+
+                    // Parse serde = (serde_json | json_column)
                     "serde" => {
                         let kind = get_path(&assign.right);
                         match kind.as_str() {
@@ -222,10 +293,25 @@ fn parse_rorm_attr(attr: &Attribute) -> Vec<AttrInfo> {
                                 attrs.push(AttrInfo::Type("String".into())); // Json type is string
                                 attrs.push(AttrInfo::SerdeJson);
                             }
+                            "json_column" => {
+                                attrs.push(AttrInfo::Type("String".into()));
+                                attrs.push(AttrInfo::SerdeJson);
+                                attrs.push(AttrInfo::JsonColumn);
+                            }
                             _ => abort!(assign.right, "Invalid serde type"),
                         }
                     }
 
+                    // Parse uuid = (binary | text)
+                    "uuid" => {
+                        let kind = get_path(&assign.right);
+                        match kind.as_str() {
+                            "binary" => attrs.push(AttrInfo::UuidText(false)),
+                            "text" => attrs.push(AttrInfo::UuidText(true)),
+                            _ => abort!(assign.right, "Invalid uuid format, expect binary or text"),
+                        }
+                    }
+
                     // Parse index = [col1, col2, ...]
                     "index" => attrs.push(AttrInfo::Index(get_path_arr(&assign.right))),
 
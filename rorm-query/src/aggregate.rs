@@ -0,0 +1,110 @@
+use std::fmt;
+
+use crate::QueryValue;
+
+/// Aggregate function of select columns, can also be compared in `Where`,
+/// e.g. `column(count!())`, `gt!(sum!("price"), 100)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Aggregate {
+    Count(Option<String>), // `COUNT(*)` if None
+    CountDistinct(String),
+    Sum(String),
+    Avg(String),
+    Min(String),
+    Max(String),
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Count(None) => write!(f, "COUNT(*)"),
+            Self::Count(Some(col)) => write!(f, "COUNT({})", col),
+            Self::CountDistinct(col) => write!(f, "COUNT(DISTINCT {})", col),
+            Self::Sum(col) => write!(f, "SUM({})", col),
+            Self::Avg(col) => write!(f, "AVG({})", col),
+            Self::Min(col) => write!(f, "MIN({})", col),
+            Self::Max(col) => write!(f, "MAX({})", col),
+        }
+    }
+}
+
+impl From<Aggregate> for QueryValue {
+    fn from(v: Aggregate) -> Self {
+        Self::Column(v.to_string())
+    }
+}
+
+/// `count!()` counts rows, `count!(col)` counts non null values,
+/// `count!(distinct col)` counts distinct non null values
+#[macro_export]
+macro_rules! count {
+    () => {
+        $crate::Aggregate::Count(None)
+    };
+    (distinct $col:expr) => {
+        $crate::Aggregate::CountDistinct($col.to_string())
+    };
+    ($col:expr) => {
+        $crate::Aggregate::Count(Some($col.to_string()))
+    };
+}
+
+#[macro_export]
+macro_rules! sum {
+    ($col:expr) => {
+        $crate::Aggregate::Sum($col.to_string())
+    };
+}
+
+#[macro_export]
+macro_rules! avg {
+    ($col:expr) => {
+        $crate::Aggregate::Avg($col.to_string())
+    };
+}
+
+#[macro_export]
+macro_rules! min {
+    ($col:expr) => {
+        $crate::Aggregate::Min($col.to_string())
+    };
+}
+
+#[macro_export]
+macro_rules! max {
+    ($col:expr) => {
+        $crate::Aggregate::Max($col.to_string())
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_aggregate() {
+        assert_eq!(&count!().to_string(), "COUNT(*)");
+
+        assert_eq!(&count!("a").to_string(), "COUNT(a)");
+
+        assert_eq!(&count!(distinct "a").to_string(), "COUNT(DISTINCT a)");
+
+        assert_eq!(&sum!("a").to_string(), "SUM(a)");
+
+        assert_eq!(&avg!("a").to_string(), "AVG(a)");
+
+        assert_eq!(&min!("a").to_string(), "MIN(a)");
+
+        assert_eq!(&max!("a").to_string(), "MAX(a)");
+
+        assert_eq!(&gt!(count!(), 1).to_string(), "(COUNT(*) > 1)");
+
+        let sql = QueryBuilder::select("ta")
+            .column("a")
+            .column(alias(sum!("b"), "total"))
+            .group_by("a")
+            .build()
+            .unwrap();
+        assert_eq!(&sql, "SELECT a, SUM(b) AS total FROM ta GROUP BY a");
+    }
+}
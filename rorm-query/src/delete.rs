@@ -1,11 +1,12 @@
 use rorm_error::Result;
 
-use crate::{lazy_impl_filer_for_struct, Filter, Where};
+use crate::{lazy_impl_filer_for_struct, Dialect, Filter, Where};
 
 #[derive(Debug, Default)]
 pub struct DeleteBuilder {
     table: String,
     filter: Filter,
+    dialect: Option<Dialect>,
 }
 
 impl DeleteBuilder {
@@ -19,6 +20,12 @@ impl DeleteBuilder {
         }
     }
 
+    /// Set dialect of database to render where condition
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
     /// Build sql
     pub fn build(&self) -> Result<String> {
         // Validate builder
@@ -34,7 +41,7 @@ impl DeleteBuilder {
         parts.push(self.table.clone());
 
         // Build filter
-        parts.push(self.filter.build()?);
+        parts.push(self.filter.build(self.dialect)?);
 
         Ok(parts.join(" "))
     }
@@ -0,0 +1,478 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use crate::{Aggregate, Dialect, QueryValue, Where};
+
+/// Arithmetic, function, `CASE` and `CAST` expression. It is rendered by dialect of the builder,
+/// see `build`, functions of mysql missing in sqlite are registered by rorm-conn.
+///
+/// Expression can be used as select column, order or group key, and compared in `Where`,
+/// e.g. `gt!(col("price") * col("count"), 100)`, `order_by(lower!("name"), true)`
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Value(QueryValue), // Column, `?` or literal
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Neg(Box<Expr>),
+    Func(String, Vec<Expr>),
+    Case(Vec<(Where, Expr)>, Option<Box<Expr>>), // (when then list, else)
+    Cast(Box<Expr>, CastType),
+    DatePart(DatePart, Box<Expr>),
+    Cond(Box<Where>),         // Condition or json value, e.g. `json_extract!`
+    Alias(Box<Expr>, String), // Select column with name
+    U64Number(Box<Expr>),     // u64 column in arithmetic, see `u64_number`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div, // Division of real numbers, integers are not truncated in sqlite
+    Rem,
+}
+
+/// Target type of `CAST`, number is truncated to integer in both databases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastType {
+    Signed,
+    Unsigned, // Same as signed in sqlite
+    Double,
+    Char,
+    Decimal(u8, u8), // (precision, scale), rounded to scale as real in sqlite
+}
+
+/// Part of date, datetime or timestamp, timestamp is stored as unix seconds in sqlite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePart {
+    Date, // e.g. `2000-01-02`
+    Year,
+    Month,
+    Day,
+}
+
+impl Expr {
+    /// Call function, e.g. `Expr::func("IFNULL", ["a", "b"])`
+    pub fn func<S, T, E>(name: S, args: T) -> Self
+    where
+        S: ToString,
+        T: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
+        Self::Func(
+            name.to_string(),
+            args.into_iter().map(|e| e.into()).collect(),
+        )
+    }
+
+    pub fn cast(self, ty: CastType) -> Self {
+        Self::Cast(Box::new(self), ty)
+    }
+
+    /// u64 column in arithmetic, sqlite stores u64 above i64::MAX as blob which is treated as 0,
+    /// so it is checked by function `u64_number` registered by rorm-conn to fail instead
+    pub fn u64_number<E: Into<Expr>>(v: E) -> Self {
+        Self::U64Number(Box::new(v.into()))
+    }
+
+    /// Name select column, e.g. `year!("created_at").alias("year")`
+    pub fn alias<S: ToString>(self, name: S) -> Self {
+        Self::Alias(Box::new(self), name.to_string())
+    }
+
+    /// Start `CASE WHEN cond THEN expr`, finished by `otherwise` or `end`
+    pub fn case_when<E: Into<Expr>>(cond: Where, then: E) -> Case {
+        Case {
+            whens: vec![(cond, then.into())],
+        }
+    }
+}
+
+/// Builder of `CASE` expression, it has at least one `WHEN` branch
+#[derive(Debug, Clone)]
+pub struct Case {
+    whens: Vec<(Where, Expr)>,
+}
+
+impl Case {
+    pub fn when<E: Into<Expr>>(mut self, cond: Where, then: E) -> Self {
+        self.whens.push((cond, then.into()));
+        self
+    }
+
+    /// Finish with `ELSE expr`
+    pub fn otherwise<E: Into<Expr>>(self, expr: E) -> Expr {
+        Expr::Case(self.whens, Some(Box::new(expr.into())))
+    }
+
+    /// Finish without `ELSE`, the result is NULL if no branch matches
+    pub fn end(self) -> Expr {
+        Expr::Case(self.whens, None)
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+impl fmt::Display for CastType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed => write!(f, "SIGNED INTEGER"),
+            Self::Unsigned => write!(f, "UNSIGNED INTEGER"),
+            Self::Double => write!(f, "DOUBLE"),
+            Self::Char => write!(f, "CHAR"),
+            Self::Decimal(p, s) => write!(f, "DECIMAL({}, {})", p, s),
+        }
+    }
+}
+
+impl DatePart {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Day => "day",
+        }
+    }
+}
+
+impl Expr {
+    /// Render by dialect, mysql syntax is used without dialect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{cast, col, year, CastType, Dialect};
+    ///
+    /// let expr = cast!(col("a") / 2, CastType::Signed);
+    /// assert_eq!(&expr.build(None), "CAST((a / 2) AS SIGNED INTEGER)");
+    /// assert_eq!(
+    ///     &expr.build(Some(Dialect::Mysql)),
+    ///     "CAST(TRUNCATE((a / 2), 0) AS SIGNED INTEGER)"
+    /// );
+    /// assert_eq!(
+    ///     &expr.build(Some(Dialect::Sqlite)),
+    ///     "CAST((CAST(a AS REAL) / 2) AS INTEGER)"
+    /// );
+    ///
+    /// assert_eq!(&year!("at").build(Some(Dialect::Sqlite)), "date_part('year', at)");
+    /// ```
+    pub fn build(&self, dialect: Option<Dialect>) -> String {
+        let is_sqlite = dialect == Some(Dialect::Sqlite);
+        match self {
+            Self::Value(v) => v.build(dialect),
+            Self::Binary(l, BinaryOp::Div, r) if is_sqlite => format!(
+                "(CAST({} AS REAL) / {})",
+                l.build(dialect),
+                r.build(dialect)
+            ),
+            Self::Binary(l, op, r) => {
+                format!("({} {} {})", l.build(dialect), op, r.build(dialect))
+            }
+            Self::Neg(v) => format!("(-{})", v.build(dialect)),
+            Self::Func(name, args) => format!(
+                "{}({})",
+                name,
+                args.iter()
+                    .map(|v| v.build(dialect))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Case(whens, otherwise) => {
+                let mut sql = "CASE".to_string();
+                for (cond, then) in whens {
+                    sql += &format!(" WHEN {} THEN {}", cond.build(dialect), then.build(dialect));
+                }
+                if let Some(v) = otherwise {
+                    sql += &format!(" ELSE {}", v.build(dialect));
+                }
+                sql + " END"
+            }
+            Self::Cast(v, ty) => {
+                let v = v.build(dialect);
+                match (dialect, ty) {
+                    (Some(Dialect::Sqlite), CastType::Signed | CastType::Unsigned) => {
+                        format!("CAST({} AS INTEGER)", v)
+                    }
+                    (Some(Dialect::Sqlite), CastType::Double) => format!("CAST({} AS REAL)", v),
+                    (Some(Dialect::Sqlite), CastType::Char) => format!("CAST({} AS TEXT)", v),
+                    (Some(Dialect::Sqlite), CastType::Decimal(_, s)) => {
+                        format!("ROUND({}, {})", v, s)
+                    }
+                    // Mysql rounds number to integer
+                    (Some(Dialect::Mysql), CastType::Signed | CastType::Unsigned) => {
+                        format!("CAST(TRUNCATE({}, 0) AS {})", v, ty)
+                    }
+                    _ => format!("CAST({} AS {})", v, ty),
+                }
+            }
+            Self::DatePart(part, v) if is_sqlite => {
+                format!("date_part('{}', {})", part.name(), v.build(dialect))
+            }
+            Self::DatePart(part, v) => {
+                format!("{}({})", part.name().to_uppercase(), v.build(dialect))
+            }
+            Self::Cond(v) => v.build(dialect),
+            Self::Alias(v, name) => format!("{} AS {}", v.build(dialect), name),
+            Self::U64Number(v) if is_sqlite => format!("u64_number({})", v.build(dialect)),
+            Self::U64Number(v) => v.build(dialect),
+        }
+    }
+}
+
+/// Rendered without dialect, see `build`
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.build(None))
+    }
+}
+
+impl From<Expr> for QueryValue {
+    fn from(v: Expr) -> Self {
+        Self::Expr(Box::new(v))
+    }
+}
+
+impl From<Where> for Expr {
+    fn from(v: Where) -> Self {
+        Self::Cond(Box::new(v))
+    }
+}
+
+macro_rules! impl_from_for_expr {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for Expr {
+                fn from(v: $ty) -> Self {
+                    Self::Value(v.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_for_expr! {bool, i8, i32, i64, u8, u32, u64, f32, f64, &str, String, QueryValue, Aggregate}
+
+/// e.g. columns of `Vec<String>`
+impl From<&String> for Expr {
+    fn from(v: &String) -> Self {
+        Self::Value(v.as_str().into())
+    }
+}
+
+macro_rules! impl_binary_op_for_expr {
+    ($($trait:ident, $method:ident, $op:ident);+) => {
+        $(
+            impl<T: Into<Expr>> $trait<T> for Expr {
+                type Output = Expr;
+
+                fn $method(self, rhs: T) -> Expr {
+                    Expr::Binary(Box::new(self), BinaryOp::$op, Box::new(rhs.into()))
+                }
+            }
+        )+
+    };
+}
+
+impl_binary_op_for_expr! {Add, add, Add; Sub, sub, Sub; Mul, mul, Mul; Div, div, Div; Rem, rem, Rem}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        Expr::Neg(Box::new(self))
+    }
+}
+
+/// Column as expression to apply operators, e.g. `col("price") * 2`
+pub fn col(name: impl ToString) -> Expr {
+    Expr::Value(QueryValue::Column(name.to_string()))
+}
+
+/// Call function by name, e.g. `func!("IFNULL", "a", 0)`
+#[macro_export]
+macro_rules! func {
+    ($name:expr $(, $arg:expr)* $(,)?) => {
+        $crate::Expr::Func($name.to_string(), vec![$($crate::Expr::from($arg)),*])
+    };
+}
+
+#[macro_export]
+macro_rules! lower {
+    ($v:expr) => {
+        $crate::func!("LOWER", $v)
+    };
+}
+
+#[macro_export]
+macro_rules! upper {
+    ($v:expr) => {
+        $crate::func!("UPPER", $v)
+    };
+}
+
+/// Count of characters, `LENGTH` of mysql counts bytes
+#[macro_export]
+macro_rules! length {
+    ($v:expr) => {
+        $crate::func!("CHAR_LENGTH", $v)
+    };
+}
+
+#[macro_export]
+macro_rules! abs {
+    ($v:expr) => {
+        $crate::func!("ABS", $v)
+    };
+}
+
+#[macro_export]
+macro_rules! coalesce {
+    ($($v:expr),+ $(,)?) => {
+        $crate::func!("COALESCE", $($v),+)
+    };
+}
+
+/// Date part of date, datetime or timestamp column, e.g. `2000-01-02`
+#[macro_export]
+macro_rules! date {
+    ($v:expr) => {
+        $crate::Expr::DatePart($crate::DatePart::Date, Box::new($crate::Expr::from($v)))
+    };
+}
+
+#[macro_export]
+macro_rules! year {
+    ($v:expr) => {
+        $crate::Expr::DatePart($crate::DatePart::Year, Box::new($crate::Expr::from($v)))
+    };
+}
+
+#[macro_export]
+macro_rules! month {
+    ($v:expr) => {
+        $crate::Expr::DatePart($crate::DatePart::Month, Box::new($crate::Expr::from($v)))
+    };
+}
+
+#[macro_export]
+macro_rules! day {
+    ($v:expr) => {
+        $crate::Expr::DatePart($crate::DatePart::Day, Box::new($crate::Expr::from($v)))
+    };
+}
+
+/// e.g. `cast!("price", CastType::Signed)`
+#[macro_export]
+macro_rules! cast {
+    ($v:expr, $ty:expr) => {
+        $crate::Expr::from($v).cast($ty)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{count, eq, gt, sql_str, CastType, Dialect, Expr};
+
+    use super::col;
+
+    #[test]
+    fn test() {
+        assert_eq!(&(col("a") + 1).to_string(), "(a + 1)");
+
+        assert_eq!(
+            &((col("a") - "b") * "?" / 2.5 % col("c")).to_string(),
+            "((((a - b) * ?) / 2.5) % c)"
+        );
+
+        assert_eq!(&(-col("a")).to_string(), "(-a)");
+
+        assert_eq!(
+            &coalesce!(lower!("name"), upper!(sql_str("x")), "?").to_string(),
+            "COALESCE(LOWER(name), UPPER('x'), ?)"
+        );
+
+        assert_eq!(&length!("a").to_string(), "CHAR_LENGTH(a)");
+
+        assert_eq!(
+            &(abs!(col("a") - 1) + func!("IFNULL", "b", 0)).to_string(),
+            "(ABS((a - 1)) + IFNULL(b, 0))"
+        );
+
+        assert_eq!(&year!(date!("at")).to_string(), "YEAR(DATE(at))");
+
+        assert_eq!(
+            &Expr::case_when(gt!("a", 10), sql_str("big"))
+                .when(gt!("a", 5), sql_str("medium"))
+                .otherwise(sql_str("small"))
+                .to_string(),
+            "CASE WHEN (a > 10) THEN 'big' WHEN (a > 5) THEN 'medium' ELSE 'small' END"
+        );
+
+        assert_eq!(
+            &Expr::case_when(eq!("a", 1), "b").end().to_string(),
+            "CASE WHEN (a = 1) THEN b END"
+        );
+
+        assert_eq!(
+            &cast!("a", CastType::Signed).to_string(),
+            "CAST(a AS SIGNED INTEGER)"
+        );
+
+        assert_eq!(
+            &cast!(Expr::from(count!()) * 2, CastType::Decimal(10, 2)).to_string(),
+            "CAST((COUNT(*) * 2) AS DECIMAL(10, 2))"
+        );
+
+        // In where
+        assert_eq!(
+            &gt!(col("price") * col("count"), "?").to_string(),
+            "((price * count) > ?)"
+        );
+    }
+
+    #[test]
+    fn test_dialect() {
+        let mysql = Some(Dialect::Mysql);
+        let sqlite = Some(Dialect::Sqlite);
+
+        assert_eq!(&(col("a") / 2).build(mysql), "(a / 2)");
+        assert_eq!(&(col("a") / 2).build(sqlite), "(CAST(a AS REAL) / 2)");
+
+        let expr = cast!("a", CastType::Unsigned);
+        assert_eq!(
+            &expr.build(mysql),
+            "CAST(TRUNCATE(a, 0) AS UNSIGNED INTEGER)"
+        );
+        assert_eq!(&expr.build(sqlite), "CAST(a AS INTEGER)");
+
+        let expr = cast!("a", CastType::Decimal(10, 2));
+        assert_eq!(&expr.build(mysql), "CAST(a AS DECIMAL(10, 2))");
+        assert_eq!(&expr.build(sqlite), "ROUND(a, 2)");
+
+        assert_eq!(&date!("at").build(mysql), "DATE(at)");
+        assert_eq!(&date!("at").build(sqlite), "date_part('date', at)");
+        assert_eq!(
+            &month!(date!("at")).build(sqlite),
+            "date_part('month', date_part('date', at))"
+        );
+
+        assert_eq!(&(col("a") + 1).alias("b").build(sqlite), "(a + 1) AS b");
+
+        let expr = Expr::u64_number("a") + "?";
+        assert_eq!(&expr.build(mysql), "(a + ?)");
+        assert_eq!(&expr.build(sqlite), "(u64_number(a) + ?)");
+    }
+}
@@ -1,19 +1,20 @@
 //! # Filter
 //!
-//! Filter contains sql `where, order_by, group_by, limit`,
+//! Filter contains sql `where, group_by, having, order_by, limit`,
 //! Use for `select, delete, update`
 //!
 //!
 use rorm_error::Result;
 
-use crate::Where;
+use crate::{Dialect, Expr, Where};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Filter {
     where_cond: Option<Where>,
-    group_bys: Vec<String>,
-    order_bys: Vec<(String, bool)>, // (column, is_asc)
-    limit: Option<(u64, u64)>,      // (limit, offset)
+    group_bys: Vec<Expr>,
+    having: Option<Where>,
+    order_bys: Vec<(Expr, bool)>, // (column or expression, is_asc)
+    limit: Option<(u64, u64)>,    // (limit, offset)
 }
 
 impl Filter {
@@ -58,9 +59,9 @@ impl Filter {
     /// ```
     pub fn group_by<S>(&mut self, col: S) -> &mut Self
     where
-        S: ToString,
+        S: Into<Expr>,
     {
-        self.group_bys.push(col.to_string());
+        self.group_bys.push(col.into());
         self
     }
 
@@ -85,9 +86,31 @@ impl Filter {
     pub fn group_bys<T, S>(&mut self, list: T) -> &mut Self
     where
         T: IntoIterator<Item = S>,
-        S: ToString,
+        S: Into<Expr>,
     {
-        self.group_bys = list.into_iter().map(|v| v.to_string()).collect();
+        self.group_bys = list.into_iter().map(|v| v.into()).collect();
+        self
+    }
+
+    /// Set having condition, which filters groups by aggregates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{QueryBuilder, alias, count, gt, sum};
+    ///
+    /// let sql = QueryBuilder::select("ta")
+    ///     .column("a")
+    ///     .column(alias(sum!("b"), "total"))
+    ///     .group_by("a")
+    ///     .having(gt!(count!(), 1))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "SELECT a, SUM(b) AS total FROM ta GROUP BY a HAVING (COUNT(*) > 1)");
+    /// ```
+    pub fn having(&mut self, cond: Where) -> &mut Self {
+        self.having = Some(cond);
         self
     }
 
@@ -110,9 +133,9 @@ impl Filter {
     /// ```
     pub fn order_by<S>(&mut self, col: S, is_asc: bool) -> &mut Self
     where
-        S: ToString,
+        S: Into<Expr>,
     {
-        self.order_bys.push((col.to_string(), is_asc));
+        self.order_bys.push((col.into(), is_asc));
         self
     }
 
@@ -135,11 +158,11 @@ impl Filter {
     pub fn order_bys<T, S>(&mut self, list: T) -> &mut Self
     where
         T: IntoIterator<Item = (S, bool)>,
-        S: ToString,
+        S: Into<Expr>,
     {
         self.order_bys = list
             .into_iter()
-            .map(|(name, is_asc)| (name.to_string(), is_asc))
+            .map(|(col, is_asc)| (col.into(), is_asc))
             .collect();
         self
     }
@@ -166,13 +189,14 @@ impl Filter {
         self
     }
 
-    pub fn build(&self) -> Result<String> {
+    pub fn build(&self, dialect: Option<Dialect>) -> Result<String> {
         let mut parts = Vec::<String>::new();
 
         // Build where
         if let Some(whe) = &self.where_cond {
+            whe.validate()?;
             parts.push("WHERE".into());
-            parts.push(whe.to_string());
+            parts.push(whe.build(dialect));
         }
 
         // Build group by
@@ -181,22 +205,29 @@ impl Filter {
             parts.push(
                 self.group_bys
                     .iter()
-                    .map(|name| name.clone())
+                    .map(|col| col.build(dialect))
                     .collect::<Vec<_>>()
                     .join(", "),
             )
         }
 
+        // Build having
+        if let Some(having) = &self.having {
+            having.validate()?;
+            parts.push("HAVING".into());
+            parts.push(having.build(dialect));
+        }
+
         // Build order by
         if !self.order_bys.is_empty() {
             parts.push("ORDER BY".into());
             parts.push(
                 self.order_bys
                     .iter()
-                    .map(|(name, is_asc)| {
+                    .map(|(col, is_asc)| {
                         format!(
                             "{} {}",
-                            name,
+                            col.build(dialect),
                             if *is_asc {
                                 "ASC".to_string()
                             } else {
@@ -232,7 +263,7 @@ macro_rules! lazy_impl_filer_for_struct {
 
             pub fn group_by<S>(&mut self, col: S) -> &mut Self
             where
-                S: ToString,
+                S: Into<$crate::Expr>,
             {
                 self.filter.group_by(col);
                 self
@@ -241,15 +272,20 @@ macro_rules! lazy_impl_filer_for_struct {
             pub fn group_bys<T, S>(&mut self, list: T) -> &mut Self
             where
                 T: IntoIterator<Item = S>,
-                S: ToString,
+                S: Into<$crate::Expr>,
             {
                 self.filter.group_bys(list);
                 self
             }
 
+            pub fn having(&mut self, cond: Where) -> &mut Self {
+                self.filter.having(cond);
+                self
+            }
+
             pub fn order_by<S>(&mut self, col: S, is_asc: bool) -> &mut Self
             where
-                S: ToString,
+                S: Into<$crate::Expr>,
             {
                 self.filter.order_by(col, is_asc);
                 self
@@ -258,7 +294,7 @@ macro_rules! lazy_impl_filer_for_struct {
             pub fn order_bys<T, S>(&mut self, list: T) -> &mut Self
             where
                 T: IntoIterator<Item = (S, bool)>,
-                S: ToString,
+                S: Into<$crate::Expr>,
             {
                 self.filter.order_bys(list);
                 self
@@ -1,12 +1,27 @@
 use rorm_error::Result;
 
-use crate::QueryValue;
+use crate::{Dialect, QueryValue};
+
+/// How a column of existing row is updated on conflict, columns without action keep existing value
+#[derive(Debug, Clone)]
+pub enum UpsertAction {
+    Excluded,          // Inserted value, `excluded.col` of sqlite, `VALUES(col)` of mysql
+    Value(QueryValue), // Expression of existing row, e.g. `"count + 1".into()`
+}
+
+#[derive(Debug, Default)]
+struct Upsert {
+    target: Vec<String>, // Conflict target columns, mysql uses any unique key instead
+    actions: Vec<(String, UpsertAction)>,
+}
 
 #[derive(Debug, Default)]
 pub struct InsertBuilder {
     table: String,
     columns: Vec<String>,
-    values_list: Vec<Vec<String>>,
+    values_list: Vec<Vec<QueryValue>>,
+    upsert: Option<Upsert>,
+    dialect: Option<Dialect>,
 }
 
 impl InsertBuilder {
@@ -94,12 +109,60 @@ impl InsertBuilder {
     where
         T: IntoIterator<Item = QueryValue>,
     {
-        self.values_list.push(
-            values
-                .into_iter()
-                .map(|v| v.to_string())
-                .collect::<Vec<String>>(),
-        );
+        self.values_list.push(values.into_iter().collect());
+        self
+    }
+
+    /// Set conflict target of upsert, rows conflicting on it are updated by actions of
+    /// `do_update`, or kept if there is no action. Mysql ignores target and reacts to any unique key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{Dialect, QueryBuilder, UpsertAction};
+    ///
+    /// let mut builder = QueryBuilder::insert("ta");
+    /// builder
+    ///     .columns(&["a", "b", "c"])
+    ///     .values(["?".into(), "?".into(), "?".into()])
+    ///     .on_conflict(["a"])
+    ///     .do_update("b", UpsertAction::Excluded)
+    ///     .do_update("c", UpsertAction::Value("c + 1".into()));
+    ///
+    /// assert_eq!(
+    ///     &builder.dialect(Dialect::Sqlite).build().unwrap(),
+    ///     "INSERT INTO ta (a, b, c) VALUES (?, ?, ?) ON CONFLICT (a) DO UPDATE SET b = excluded.b, c = c + 1"
+    /// );
+    /// assert_eq!(
+    ///     &builder.dialect(Dialect::Mysql).build().unwrap(),
+    ///     "INSERT INTO ta (a, b, c) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE b = VALUES(b), c = c + 1"
+    /// );
+    /// ```
+    pub fn on_conflict<T, S>(&mut self, target: T) -> &mut Self
+    where
+        T: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.upsert.get_or_insert_with(Default::default).target =
+            target.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Append update action of upsert
+    pub fn do_update<S>(&mut self, col: S, action: UpsertAction) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.upsert
+            .get_or_insert_with(Default::default)
+            .actions
+            .push((col.to_string(), action));
+        self
+    }
+
+    /// Set dialect of database, required by upsert
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = Some(dialect);
         self
     }
 
@@ -122,7 +185,13 @@ impl InsertBuilder {
             parts.push(
                 self.values_list
                     .iter()
-                    .map(|values| format!("({})", values.join(", ")))
+                    .map(|values| {
+                        let values = values
+                            .iter()
+                            .map(|v| v.build(self.dialect))
+                            .collect::<Vec<_>>();
+                        format!("({})", values.join(", "))
+                    })
                     .collect::<Vec<String>>()
                     .join(", "),
             );
@@ -130,11 +199,69 @@ impl InsertBuilder {
             parts.push("DEFAULT VALUES".into());
         }
 
+        // Build upsert
+        if let Some(upsert) = &self.upsert {
+            parts.push(self.build_upsert(upsert)?);
+        }
+
         Ok(parts.join(" "))
     }
 
+    fn build_upsert(&self, upsert: &Upsert) -> Result<String> {
+        let set = |excluded: fn(&str) -> String| {
+            upsert
+                .actions
+                .iter()
+                .map(|(col, action)| match action {
+                    UpsertAction::Excluded => format!("{} = {}", col, excluded(col)),
+                    UpsertAction::Value(v) => format!("{} = {}", col, v.build(self.dialect)),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let sql = match self.dialect {
+            Some(Dialect::Sqlite) => {
+                let target = if upsert.target.is_empty() {
+                    "".into()
+                } else {
+                    format!(" ({})", upsert.target.join(", "))
+                };
+
+                if upsert.actions.is_empty() {
+                    format!("ON CONFLICT{} DO NOTHING", target)
+                } else {
+                    format!(
+                        "ON CONFLICT{} DO UPDATE SET {}",
+                        target,
+                        set(|col| format!("excluded.{}", col))
+                    )
+                }
+            }
+            Some(Dialect::Mysql) => {
+                if upsert.actions.is_empty() {
+                    // Assign a column to itself, `INSERT IGNORE` also ignores other errors
+                    let col = upsert.target.first().unwrap_or(&self.columns[0]);
+                    format!("ON DUPLICATE KEY UPDATE {} = {}", col, col)
+                } else {
+                    format!(
+                        "ON DUPLICATE KEY UPDATE {}",
+                        set(|col| format!("VALUES({})", col))
+                    )
+                }
+            }
+            None => return Err(rorm_error::query_builder!("Upsert requires dialect")),
+        };
+
+        Ok(sql)
+    }
+
     /// Validate builder
     fn validate(&self) -> Result<()> {
+        if self.upsert.is_some() && self.columns.is_empty() {
+            return Err(rorm_error::query_builder!("Upsert empty columns"));
+        }
+
         for values in &self.values_list {
             if values.len() != self.columns.len() {
                 return Err(rorm_error::query_builder!(
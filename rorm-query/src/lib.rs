@@ -1,4 +1,6 @@
+mod aggregate;
 mod delete;
+mod expr;
 mod filter;
 mod insert;
 mod query_value;
@@ -6,15 +8,27 @@ mod select;
 mod update;
 mod where_cond;
 
+pub use aggregate::Aggregate;
 pub use delete::DeleteBuilder;
-pub use insert::InsertBuilder;
-pub use query_value::{sql_str, QueryValue};
-pub use select::SelectBuilder;
+pub use expr::{col, BinaryOp, Case, CastType, DatePart, Expr};
+pub use insert::{InsertBuilder, UpsertAction};
+pub use query_value::{
+    alias, escape_like, like_contains, like_ends_with, like_starts_with, sql_str, QueryValue,
+    LIKE_ESCAPE,
+};
+pub use select::{JoinKind, SelectBuilder};
 pub use update::UpdateBuilder;
 pub use where_cond::Where;
 
 use filter::Filter;
 
+/// Database of sql, only needed by syntax without common form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Mysql,
+    Sqlite,
+}
+
 #[derive(Debug)]
 pub struct QueryBuilder {}
 
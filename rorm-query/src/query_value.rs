@@ -1,3 +1,5 @@
+use crate::{Dialect, Expr};
+
 macro_rules! impl_from_for_signedint {
     ($($ty:ty),+) => {
         $(
@@ -46,7 +48,7 @@ macro_rules! impl_from_for_column {
     };
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum QueryValue {
     Bool(bool),
     SignedInt(i64),
@@ -54,10 +56,12 @@ pub enum QueryValue {
     Float(f64),
     Column(String),
     Str(String),
+    Expr(Box<Expr>),
 }
 
-impl ToString for QueryValue {
-    fn to_string(&self) -> String {
+impl QueryValue {
+    /// Render by dialect, only expression differs
+    pub fn build(&self, dialect: Option<Dialect>) -> String {
         match &self {
             Self::Bool(v) => v.to_string(),
             Self::SignedInt(v) => v.to_string(),
@@ -65,10 +69,18 @@ impl ToString for QueryValue {
             Self::Float(v) => v.to_string(),
             Self::Column(v) => v.to_string(),
             Self::Str(v) => format!("'{}'", v),
+            Self::Expr(v) => v.build(dialect),
         }
     }
 }
 
+/// Rendered without dialect, see `build`
+impl ToString for QueryValue {
+    fn to_string(&self) -> String {
+        self.build(None)
+    }
+}
+
 impl From<bool> for QueryValue {
     fn from(v: bool) -> Self {
         Self::Bool(v)
@@ -83,3 +95,37 @@ impl_from_for_column! {&str, String}
 pub fn sql_str(v: impl ToString) -> QueryValue {
     QueryValue::Str(v.to_string())
 }
+
+/// Table with alias, e.g. `alias("user", "u")` => `user AS u`, columns of it are referred as `u.name`
+pub fn alias(table: impl ToString, alias: impl ToString) -> String {
+    format!("{} AS {}", table.to_string(), alias.to_string())
+}
+
+/// Escape char of `like_escape!`, not special in string literal of any database unlike `\\`
+pub const LIKE_ESCAPE: char = '!';
+
+/// Escape `%`, `_` and escape char, so input is matched literally by `like_escape!`
+pub fn escape_like(input: &str) -> String {
+    let mut s = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | LIKE_ESCAPE) {
+            s.push(LIKE_ESCAPE);
+        }
+        s.push(c);
+    }
+
+    s
+}
+
+/// Pattern of `like_escape!` matching text containing input, e.g. `50%` => `%50!%%`
+pub fn like_contains(input: &str) -> String {
+    format!("%{}%", escape_like(input))
+}
+
+pub fn like_starts_with(input: &str) -> String {
+    format!("{}%", escape_like(input))
+}
+
+pub fn like_ends_with(input: &str) -> String {
+    format!("%{}", escape_like(input))
+}
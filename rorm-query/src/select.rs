@@ -1,12 +1,43 @@
+use std::{any::Any, fmt, sync::Arc};
+
 use rorm_error::Result;
 
-use crate::{lazy_impl_filer_for_struct, Filter, Where};
+use crate::{lazy_impl_filer_for_struct, Dialect, Expr, Filter, Where};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Cross,
+}
+
+#[derive(Debug, Clone)]
+struct Join {
+    kind: JoinKind,
+    table: String,
+    on: Option<Where>, // None for cross join
+}
+
+/// Params bound by subquery, opaque to query builder, e.g. `Vec<Value>` of rorm
+#[derive(Clone)]
+struct BoundParams(Arc<dyn Any + Send + Sync>);
 
-#[derive(Debug, Default)]
+impl fmt::Debug for BoundParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BoundParams")
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct SelectBuilder {
     table: String,
-    columns: Vec<String>,
+    is_distinct: bool,
+    columns: Vec<Expr>, // Column names or expressions
+    joins: Vec<Join>,
     filter: Filter,
+    params: Option<BoundParams>,
+    dialect: Option<Dialect>,
 }
 
 impl SelectBuilder {
@@ -37,9 +68,9 @@ impl SelectBuilder {
     /// ```
     pub fn column<S>(&mut self, col: S) -> &mut Self
     where
-        S: ToString,
+        S: Into<Expr>,
     {
-        self.columns.push(col.to_string());
+        self.columns.push(col.into());
         self
     }
 
@@ -51,7 +82,7 @@ impl SelectBuilder {
     /// use rorm_query::{QueryBuilder, sql_str};
     ///
     /// let a = QueryBuilder::select("ta")
-    ///     .columns(&["a", "b"])
+    ///     .columns(["a", "b"])
     ///     .build()
     ///     .unwrap();
     ///
@@ -60,34 +91,206 @@ impl SelectBuilder {
     pub fn columns<T, S>(&mut self, cols: T) -> &mut Self
     where
         T: IntoIterator<Item = S>,
+        S: Into<Expr>,
+    {
+        self.columns = cols.into_iter().map(|s| s.into()).collect::<Vec<Expr>>();
+        self
+    }
+
+    /// Remove duplicate rows
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::QueryBuilder;
+    ///
+    /// let sql = QueryBuilder::select("ta")
+    ///     .column("a")
+    ///     .distinct()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "SELECT DISTINCT a FROM ta ");
+    /// ```
+    pub fn distinct(&mut self) -> &mut Self {
+        self.is_distinct = true;
+        self
+    }
+
+    /// Bind params of `?` in the builder used as subquery, the outer query gets them at the
+    /// position of subquery by `Where::subquery_params`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{and, eq, exists, QueryBuilder};
+    ///
+    /// let mut sub = QueryBuilder::select("tb");
+    /// sub.column("1").where_cond(eq!("b", "?")).bind_params(vec![2]);
+    ///
+    /// let cond = and!(eq!("a", "?"), exists!(sub));
+    /// assert_eq!(cond.subquery_params::<Vec<i32>>(), vec![(1, &vec![2])]);
+    /// ```
+    pub fn bind_params<P: Any + Send + Sync>(&mut self, params: P) -> &mut Self {
+        self.params = Some(BoundParams(Arc::new(params)));
+        self
+    }
+
+    /// Params bound by `bind_params`, None if not bound or not of type `P`
+    pub fn bound_params<P: Any>(&self) -> Option<&P> {
+        self.params.as_ref().and_then(|p| p.0.downcast_ref())
+    }
+
+    /// Append join, `on` is required by all kinds except cross join,
+    /// table may be aliased by `alias`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{alias, eq, JoinKind, QueryBuilder};
+    ///
+    /// let sql = QueryBuilder::select("ta")
+    ///     .column("ta.a")
+    ///     .column("b.c")
+    ///     .join(JoinKind::Left, alias("tb", "b"), Some(eq!("ta.id", "b.a_id")))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "SELECT ta.a, b.c FROM ta LEFT JOIN tb AS b ON (ta.id = b.a_id) ");
+    /// ```
+    pub fn join<S>(&mut self, kind: JoinKind, table: S, on: Option<Where>) -> &mut Self
+    where
         S: ToString,
     {
-        self.columns = cols
-            .into_iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        self.joins.push(Join {
+            kind,
+            table: table.to_string(),
+            on,
+        });
+        self
+    }
+
+    /// Append inner join
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::{eq, gt, QueryBuilder};
+    ///
+    /// let sql = QueryBuilder::select("ta")
+    ///     .column("ta.a")
+    ///     .inner_join("tb", eq!("ta.id", "tb.a_id"))
+    ///     .where_cond(gt!("tb.b", 1))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "SELECT ta.a FROM ta INNER JOIN tb ON (ta.id = tb.a_id) WHERE (tb.b > 1)");
+    /// ```
+    pub fn inner_join<S>(&mut self, table: S, on: Where) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.join(JoinKind::Inner, table, Some(on))
+    }
+
+    /// Append left join
+    pub fn left_join<S>(&mut self, table: S, on: Where) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.join(JoinKind::Left, table, Some(on))
+    }
+
+    /// Append right join, requires sqlite 3.39 or later
+    pub fn right_join<S>(&mut self, table: S, on: Where) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.join(JoinKind::Right, table, Some(on))
+    }
+
+    /// Append cross join
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::QueryBuilder;
+    ///
+    /// let sql = QueryBuilder::select("ta")
+    ///     .columns(["ta.a", "tb.b"])
+    ///     .cross_join("tb")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "SELECT ta.a, tb.b FROM ta CROSS JOIN tb ");
+    /// ```
+    pub fn cross_join<S>(&mut self, table: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.join(JoinKind::Cross, table, None)
+    }
+
+    /// Set dialect of database to render syntax without common form, e.g. `null_safe_eq!`,
+    /// subqueries are rendered by dialect of outer query
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = Some(dialect);
         self
     }
 
     /// Build sql
     pub fn build(&self) -> Result<String> {
+        self.build_by(None)
+    }
+
+    /// Build by dialect of outer query if it's a subquery, or by dialect of the builder
+    pub(crate) fn build_by(&self, dialect: Option<Dialect>) -> Result<String> {
         // Validate builder
         self.validate()?;
 
+        let dialect = dialect.or(self.dialect);
+
         let mut parts = Vec::<String>::new();
 
         // Build prefix
         parts.push("SELECT".into());
+        if self.is_distinct {
+            parts.push("DISTINCT".into());
+        }
 
         // Build columns
-        parts.push(self.columns.join(", "));
+        parts.push(
+            self.columns
+                .iter()
+                .map(|col| col.build(dialect))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
 
         // Build table
         parts.push("FROM".into());
         parts.push(self.table.clone());
 
+        // Build joins
+        for join in &self.joins {
+            parts.push(
+                match join.kind {
+                    JoinKind::Inner => "INNER JOIN",
+                    JoinKind::Left => "LEFT JOIN",
+                    JoinKind::Right => "RIGHT JOIN",
+                    JoinKind::Cross => "CROSS JOIN",
+                }
+                .into(),
+            );
+            parts.push(join.table.clone());
+            if let Some(on) = &join.on {
+                parts.push("ON".into());
+                parts.push(on.build(dialect));
+            }
+        }
+
         // Build filter
-        parts.push(self.filter.build()?);
+        parts.push(self.filter.build(dialect)?);
 
         Ok(parts.join(" "))
     }
@@ -98,6 +301,27 @@ impl SelectBuilder {
             return Err(rorm_error::query_builder!("Select empty columns"));
         }
 
+        for join in &self.joins {
+            match (join.kind, &join.on) {
+                (JoinKind::Cross, Some(_)) => {
+                    return Err(rorm_error::query_builder!(
+                        "Cross join `{}` must not have on condition",
+                        join.table
+                    ))
+                }
+                (JoinKind::Inner | JoinKind::Left | JoinKind::Right, None) => {
+                    return Err(rorm_error::query_builder!(
+                        "Join `{}` must have on condition",
+                        join.table
+                    ))
+                }
+                _ => {}
+            }
+            if let Some(on) = &join.on {
+                on.validate()?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -1,12 +1,13 @@
 use rorm_error::Result;
 
-use crate::{lazy_impl_filer_for_struct, Filter, QueryValue, Where};
+use crate::{lazy_impl_filer_for_struct, Dialect, Filter, QueryValue, Where};
 
 #[derive(Debug, Default)]
 pub struct UpdateBuilder {
     table: String,
-    kvs: Vec<(String, String)>,
+    kvs: Vec<(String, QueryValue)>,
     filter: Filter,
+    dialect: Option<Dialect>,
 }
 
 impl UpdateBuilder {
@@ -35,11 +36,50 @@ impl UpdateBuilder {
     ///
     /// assert_eq!(&sql, "UPDATE ta SET a = 1, b = 'abc' ");
     /// ```
+    ///
+    /// Value can be an expression of columns
+    ///
+    /// ```
+    /// use rorm_query::QueryBuilder;
+    ///
+    /// let sql = QueryBuilder::update("ta")
+    ///     .set("a", "b".into())
+    ///     .set("c", "COALESCE(c, ?)".into())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "UPDATE ta SET a = b, c = COALESCE(c, ?) ");
+    /// ```
     pub fn set<S>(&mut self, col: S, val: QueryValue) -> &mut Self
     where
         S: ToString,
     {
-        self.kvs.push((col.to_string(), val.to_string()));
+        self.kvs.push((col.to_string(), val));
+        self
+    }
+
+    /// Increase column by value atomically, decrease by negative value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rorm_query::QueryBuilder;
+    ///
+    /// let sql = QueryBuilder::update("ta")
+    ///     .increment("a", 1.into())
+    ///     .increment("b", "?".into())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&sql, "UPDATE ta SET a = a + 1, b = b + ? ");
+    /// ```
+    pub fn increment<S>(&mut self, col: S, by: QueryValue) -> &mut Self
+    where
+        S: ToString,
+    {
+        let col = col.to_string();
+        let expr = format!("{} + {}", col, by.to_string());
+        self.kvs.push((col, QueryValue::Column(expr)));
         self
     }
 
@@ -62,10 +102,13 @@ impl UpdateBuilder {
         T: IntoIterator<Item = (S, QueryValue)>,
         S: ToString,
     {
-        self.kvs = kvs
-            .into_iter()
-            .map(|(k, v)| (k.to_string(), v.to_string()))
-            .collect();
+        self.kvs = kvs.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        self
+    }
+
+    /// Set dialect of database to render where condition
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = Some(dialect);
         self
     }
 
@@ -85,13 +128,13 @@ impl UpdateBuilder {
         parts.push(
             self.kvs
                 .iter()
-                .map(|(k, v)| format!("{} = {}", k, v))
+                .map(|(k, v)| format!("{} = {}", k, v.build(self.dialect)))
                 .collect::<Vec<_>>()
                 .join(", "),
         );
 
         // Build filter
-        parts.push(self.filter.build()?);
+        parts.push(self.filter.build(self.dialect)?);
 
         Ok(parts.join(" "))
     }
@@ -1,11 +1,16 @@
-use crate::QueryValue;
+use std::any::Any;
 
-#[derive(Debug)]
+use rorm_error::Result;
+
+use crate::{Dialect, QueryValue, SelectBuilder, LIKE_ESCAPE};
+
+#[derive(Debug, Clone)]
 pub enum Where {
     And(Box<Where>, Box<Where>),
     Or(Box<Where>, Box<Where>),
     Not(Box<Where>),
     Eq(Box<Where>, Box<Where>),
+    NullSafeEq(Box<Where>, Box<Where>), // Equal or both null, rendered by dialect
     Ne(Box<Where>, Box<Where>),
     Lt(Box<Where>, Box<Where>),
     Le(Box<Where>, Box<Where>),
@@ -14,6 +19,20 @@ pub enum Where {
     Between(Box<Where>, Box<Where>, Box<Where>),
     In(Box<Where>, Vec<Where>),
     Like(Box<Where>, Box<Where>),
+    NotLike(Box<Where>, Box<Where>),
+    ILike(Box<Where>, Box<Where>),      // Case insensitive like
+    LikeEscape(Box<Where>, Box<Where>), // Like with `LIKE_ESCAPE` as escape char
+    Glob(Box<Where>, Box<Where>),       // Sqlite only, case sensitive unix glob
+    Regexp(Box<Where>, Box<Where>),
+    IsNull(Box<Where>),
+    IsNotNull(Box<Where>),
+    JsonExtract(Box<Where>, String), // (json, path), value at path
+    JsonExtractText(Box<Where>, String), // (json, path), value at path as text
+    JsonContains(Box<Where>, Box<Where>, Option<String>), // (json, candidate, path)
+    InSelect(Box<Where>, Box<SelectBuilder>),
+    Exists(Box<SelectBuilder>),
+    NotExists(Box<SelectBuilder>),
+    Select(Box<SelectBuilder>), // Scalar subquery, e.g. in comparisons
     Value(QueryValue),
 }
 
@@ -23,38 +42,251 @@ impl<T: Into<QueryValue>> From<T> for Where {
     }
 }
 
+impl Where {
+    /// Check that all subqueries can be built, called by builders before `to_string`
+    pub fn validate(&self) -> Result<()> {
+        match &self {
+            Self::And(l, r)
+            | Self::Or(l, r)
+            | Self::Eq(l, r)
+            | Self::NullSafeEq(l, r)
+            | Self::Ne(l, r)
+            | Self::Lt(l, r)
+            | Self::Le(l, r)
+            | Self::Gt(l, r)
+            | Self::Ge(l, r)
+            | Self::Like(l, r)
+            | Self::NotLike(l, r)
+            | Self::ILike(l, r)
+            | Self::LikeEscape(l, r)
+            | Self::Glob(l, r)
+            | Self::Regexp(l, r)
+            | Self::JsonContains(l, r, _) => {
+                l.validate()?;
+                r.validate()
+            }
+            Self::Between(var, l, r) => {
+                var.validate()?;
+                l.validate()?;
+                r.validate()
+            }
+            Self::In(var, list) => {
+                var.validate()?;
+                list.iter().try_for_each(|v| v.validate())
+            }
+            Self::Not(v)
+            | Self::IsNull(v)
+            | Self::IsNotNull(v)
+            | Self::JsonExtract(v, _)
+            | Self::JsonExtractText(v, _) => v.validate(),
+            Self::InSelect(var, sub) => {
+                var.validate()?;
+                sub.build().map(|_| ())
+            }
+            Self::Exists(sub) | Self::NotExists(sub) | Self::Select(sub) => sub.build().map(|_| ()),
+            Self::Value(_) => Ok(()),
+        }
+    }
+
+    /// Params bound by subqueries with the number of `?` before each of them in sql, in order,
+    /// `?` of subqueries without bound params are counted as `?` of this condition
+    pub fn subquery_params<P: Any>(&self) -> Vec<(usize, &P)> {
+        let mut count = 0;
+        let mut list = vec![];
+        self.collect_subquery_params(&mut count, &mut list);
+        list
+    }
+
+    fn collect_subquery_params<'a, P: Any>(
+        &'a self,
+        count: &mut usize,
+        list: &mut Vec<(usize, &'a P)>,
+    ) {
+        match &self {
+            Self::And(l, r)
+            | Self::Or(l, r)
+            | Self::Eq(l, r)
+            | Self::NullSafeEq(l, r)
+            | Self::Ne(l, r)
+            | Self::Lt(l, r)
+            | Self::Le(l, r)
+            | Self::Gt(l, r)
+            | Self::Ge(l, r)
+            | Self::Like(l, r)
+            | Self::NotLike(l, r)
+            | Self::ILike(l, r)
+            | Self::LikeEscape(l, r)
+            | Self::Glob(l, r)
+            | Self::Regexp(l, r)
+            | Self::JsonContains(l, r, _) => {
+                l.collect_subquery_params(count, list);
+                r.collect_subquery_params(count, list);
+            }
+            Self::Between(var, l, r) => {
+                var.collect_subquery_params(count, list);
+                l.collect_subquery_params(count, list);
+                r.collect_subquery_params(count, list);
+            }
+            // Empty list is rendered without var
+            Self::In(_, values) if values.is_empty() => {}
+            Self::In(var, values) => {
+                var.collect_subquery_params(count, list);
+                for v in values {
+                    v.collect_subquery_params(count, list);
+                }
+            }
+            Self::Not(v)
+            | Self::IsNull(v)
+            | Self::IsNotNull(v)
+            | Self::JsonExtract(v, _)
+            | Self::JsonExtractText(v, _) => v.collect_subquery_params(count, list),
+            Self::InSelect(var, sub) => {
+                var.collect_subquery_params(count, list);
+                collect_sub_params(sub, count, list);
+            }
+            Self::Exists(sub) | Self::NotExists(sub) | Self::Select(sub) => {
+                collect_sub_params(sub, count, list)
+            }
+            Self::Value(QueryValue::Column(v)) => *count += count_placeholders(v),
+            Self::Value(v @ QueryValue::Expr(_)) => *count += count_placeholders(&v.to_string()),
+            Self::Value(_) => {}
+        }
+    }
+}
+
+fn collect_sub_params<'a, P: Any>(
+    sub: &'a SelectBuilder,
+    count: &mut usize,
+    list: &mut Vec<(usize, &'a P)>,
+) {
+    match sub.bound_params() {
+        Some(params) => list.push((*count, params)),
+        None => *count += count_placeholders(&build_sub(sub, None)),
+    }
+}
+
+/// Count `?` out of string literals
+fn count_placeholders(sql: &str) -> usize {
+    let mut is_quoted = false;
+    sql.chars()
+        .filter(|&c| {
+            if c == '\'' {
+                is_quoted = !is_quoted;
+            }
+            c == '?' && !is_quoted
+        })
+        .count()
+}
+
+/// Rendered without dialect, see `build`
 impl ToString for Where {
     fn to_string(&self) -> String {
+        self.build(None)
+    }
+}
+
+impl Where {
+    /// Render by dialect, syntax without common form is rendered as standard sql if dialect is
+    /// none. Subqueries failed to build are rendered as empty, use `validate` to check them
+    pub fn build(&self, dialect: Option<Dialect>) -> String {
         match &self {
-            Self::And(l, r) => format!("({} AND {})", l.to_string(), r.to_string()),
-            Self::Or(l, r) => format!("({} OR {})", l.to_string(), r.to_string()),
-            Self::Not(v) => format!("(NOT {})", v.to_string()),
-            Self::Eq(l, r) => format!("({} = {})", l.to_string(), r.to_string()),
-            Self::Ne(l, r) => format!("({} <> {})", l.to_string(), r.to_string()),
-            Self::Lt(l, r) => format!("({} < {})", l.to_string(), r.to_string()),
-            Self::Le(l, r) => format!("({} <= {})", l.to_string(), r.to_string()),
-            Self::Gt(l, r) => format!("({} > {})", l.to_string(), r.to_string()),
-            Self::Ge(l, r) => format!("({} >= {})", l.to_string(), r.to_string()),
+            Self::And(l, r) => format!("({} AND {})", l.build(dialect), r.build(dialect)),
+            Self::Or(l, r) => format!("({} OR {})", l.build(dialect), r.build(dialect)),
+            Self::Not(v) => format!("(NOT {})", v.build(dialect)),
+            Self::Eq(l, r) => format!("({} = {})", l.build(dialect), r.build(dialect)),
+            Self::NullSafeEq(l, r) => {
+                let op = match dialect {
+                    Some(Dialect::Mysql) => "<=>",
+                    Some(Dialect::Sqlite) => "IS",
+                    None => "IS NOT DISTINCT FROM",
+                };
+                format!("({} {} {})", l.build(dialect), op, r.build(dialect))
+            }
+            Self::Ne(l, r) => format!("({} <> {})", l.build(dialect), r.build(dialect)),
+            Self::Lt(l, r) => format!("({} < {})", l.build(dialect), r.build(dialect)),
+            Self::Le(l, r) => format!("({} <= {})", l.build(dialect), r.build(dialect)),
+            Self::Gt(l, r) => format!("({} > {})", l.build(dialect), r.build(dialect)),
+            Self::Ge(l, r) => format!("({} >= {})", l.build(dialect), r.build(dialect)),
             Self::Between(var, l, r) => format!(
                 "({} BETWEEN {} AND {})",
-                var.to_string(),
-                l.to_string(),
-                r.to_string()
+                var.build(dialect),
+                l.build(dialect),
+                r.build(dialect)
             ),
+            // `IN ()` is invalid sql
+            Self::In(_, list) if list.is_empty() => "(1 = 0)".into(),
             Self::In(var, list) => format!(
                 "({} IN ({}))",
-                var.to_string(),
+                var.build(dialect),
                 list.iter()
-                    .map(|v| v.to_string())
+                    .map(|v| v.build(dialect))
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
-            Self::Like(var, lik) => format!("({} LIKE {})", var.to_string(), lik.to_string()),
-            Self::Value(v) => v.to_string(),
+            Self::Like(var, lik) => format!("({} LIKE {})", var.build(dialect), lik.build(dialect)),
+            Self::NotLike(var, lik) => {
+                format!("({} NOT LIKE {})", var.build(dialect), lik.build(dialect))
+            }
+            Self::ILike(var, lik) => format!(
+                "(LOWER({}) LIKE LOWER({}))",
+                var.build(dialect),
+                lik.build(dialect)
+            ),
+            Self::LikeEscape(var, lik) => format!(
+                "({} LIKE {} ESCAPE '{}')",
+                var.build(dialect),
+                lik.build(dialect),
+                LIKE_ESCAPE
+            ),
+            Self::Glob(var, pattern) => {
+                format!("({} GLOB {})", var.build(dialect), pattern.build(dialect))
+            }
+            Self::Regexp(var, pattern) => {
+                format!("({} REGEXP {})", var.build(dialect), pattern.build(dialect))
+            }
+            Self::IsNull(var) => format!("({} IS NULL)", var.build(dialect)),
+            Self::IsNotNull(var) => format!("({} IS NOT NULL)", var.build(dialect)),
+            Self::JsonExtract(var, path) => {
+                format!("json_extract({}, {})", var.build(dialect), quote_path(path))
+            }
+            Self::JsonExtractText(var, path) => {
+                format!("({} ->> {})", var.build(dialect), quote_path(path))
+            }
+            Self::JsonContains(var, candidate, path) => match path {
+                Some(path) => format!(
+                    "json_contains({}, {}, {})",
+                    var.build(dialect),
+                    candidate.build(dialect),
+                    quote_path(path)
+                ),
+                None => format!(
+                    "json_contains({}, {})",
+                    var.build(dialect),
+                    candidate.build(dialect)
+                ),
+            },
+            Self::InSelect(var, sub) => {
+                format!("({} IN ({}))", var.build(dialect), build_sub(sub, dialect))
+            }
+            Self::Exists(sub) => format!("(EXISTS ({}))", build_sub(sub, dialect)),
+            Self::NotExists(sub) => format!("(NOT EXISTS ({}))", build_sub(sub, dialect)),
+            Self::Select(sub) => format!("({})", build_sub(sub, dialect)),
+            Self::Value(v) => v.build(dialect),
         }
     }
 }
 
+fn build_sub(sub: &SelectBuilder, dialect: Option<Dialect>) -> String {
+    sub.build_by(dialect)
+        .map(|sql| sql.trim_end().to_string())
+        .unwrap_or_default()
+}
+
+/// Json path must be literal in mysql
+fn quote_path(path: &str) -> String {
+    format!("'{}'", path.replace('\'', "''"))
+}
+
 #[macro_export]
 macro_rules! and {
     ($left:expr, $right:expr) => {
@@ -99,6 +331,18 @@ macro_rules! eq {
     };
 }
 
+/// Equal, or both are null, e.g. `null_safe_eq!("a", "?")` with a nullable param,
+/// rendered as `<=>` in mysql, `IS` in sqlite and `IS NOT DISTINCT FROM` without dialect
+#[macro_export]
+macro_rules! null_safe_eq {
+    ($left:expr, $right:expr) => {
+        $crate::Where::NullSafeEq(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
 #[macro_export]
 macro_rules! ne {
     ($left:expr, $right:expr) => {
@@ -177,6 +421,145 @@ macro_rules! like {
     };
 }
 
+#[macro_export]
+macro_rules! not_like {
+    ($left:expr, $right:expr) => {
+        $crate::Where::NotLike(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
+/// Case insensitive like, both sides are compared in lower case
+#[macro_export]
+macro_rules! ilike {
+    ($left:expr, $right:expr) => {
+        $crate::Where::ILike(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
+/// Like with pattern built by `like_contains`, `like_starts_with` or `like_ends_with`,
+/// e.g. `like_escape!("name", "?")` with param `like_contains(input)`
+#[macro_export]
+macro_rules! like_escape {
+    ($left:expr, $right:expr) => {
+        $crate::Where::LikeEscape(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
+/// Sqlite only, e.g. `glob!("path", sql_str("*.rs"))`
+#[macro_export]
+macro_rules! glob {
+    ($left:expr, $right:expr) => {
+        $crate::Where::Glob(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
+/// Whether var matches regular expression, function of sqlite is registered by rorm-conn
+#[macro_export]
+macro_rules! regexp {
+    ($left:expr, $right:expr) => {
+        $crate::Where::Regexp(
+            Box::new($crate::literal!($left)),
+            Box::new($crate::literal!($right)),
+        )
+    };
+}
+
+/// Comparing with null by `=` is never true, use `is_null!("a")` instead
+#[macro_export]
+macro_rules! is_null {
+    ($var:expr) => {
+        $crate::Where::IsNull(Box::new($crate::literal!($var)))
+    };
+}
+
+#[macro_export]
+macro_rules! is_not_null {
+    ($var:expr) => {
+        $crate::Where::IsNotNull(Box::new($crate::literal!($var)))
+    };
+}
+
+/// Extract value at path, e.g. `json_extract!("profile", "$.age")`
+#[macro_export]
+macro_rules! json_extract {
+    ($var:expr, $path:expr) => {
+        $crate::Where::JsonExtract(Box::new($crate::literal!($var)), $path.to_string())
+    };
+}
+
+/// Extract value at path as unquoted text, same as `->>`
+#[macro_export]
+macro_rules! json_extract_text {
+    ($var:expr, $path:expr) => {
+        $crate::Where::JsonExtractText(Box::new($crate::literal!($var)), $path.to_string())
+    };
+}
+
+/// Whether candidate json is contained in json, or in the value at path
+#[macro_export]
+macro_rules! json_contains {
+    ($var:expr, $candidate:expr) => {
+        $crate::Where::JsonContains(
+            Box::new($crate::literal!($var)),
+            Box::new($crate::literal!($candidate)),
+            None,
+        )
+    };
+    ($var:expr, $candidate:expr, $path:expr) => {
+        $crate::Where::JsonContains(
+            Box::new($crate::literal!($var)),
+            Box::new($crate::literal!($candidate)),
+            Some($path.to_string()),
+        )
+    };
+}
+
+/// Whether var is in the result of subquery, e.g. `in_select!("id", sub)`,
+/// subquery is a `SelectBuilder`, params bound by `SelectBuilder::bind_params` are carried with it
+#[macro_export]
+macro_rules! in_select {
+    ($var:expr, $sub:expr) => {
+        $crate::Where::InSelect(
+            Box::new($crate::literal!($var)),
+            Box::new($crate::SelectBuilder::from($sub)),
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! exists {
+    ($sub:expr) => {
+        $crate::Where::Exists(Box::new($crate::SelectBuilder::from($sub)))
+    };
+}
+
+#[macro_export]
+macro_rules! not_exists {
+    ($sub:expr) => {
+        $crate::Where::NotExists(Box::new($crate::SelectBuilder::from($sub)))
+    };
+}
+
+/// Scalar subquery, e.g. `gt!("age", subquery!(sub))`
+#[macro_export]
+macro_rules! subquery {
+    ($sub:expr) => {
+        $crate::Where::Select(Box::new($crate::SelectBuilder::from($sub)))
+    };
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -216,6 +599,140 @@ mod test {
 
         assert_eq!(&r#in!("a", [1, 2, 3]).to_string(), "(a IN (1, 2, 3))");
 
+        assert_eq!(
+            &Where::In(Box::new(literal!("a")), vec![]).to_string(),
+            "(1 = 0)"
+        );
+
         assert_eq!(&like!("a", sql_str("abc")).to_string(), "(a LIKE 'abc')");
+
+        assert_eq!(&not_like!("a", "?").to_string(), "(a NOT LIKE ?)");
+
+        assert_eq!(&ilike!("a", "?").to_string(), "(LOWER(a) LIKE LOWER(?))");
+
+        assert_eq!(&like_escape!("a", "?").to_string(), "(a LIKE ? ESCAPE '!')");
+
+        assert_eq!(&glob!("a", sql_str("*.rs")).to_string(), "(a GLOB '*.rs')");
+
+        assert_eq!(&regexp!("a", "?").to_string(), "(a REGEXP ?)");
+
+        assert_eq!(&is_null!("a").to_string(), "(a IS NULL)");
+
+        assert_eq!(
+            &or!(is_not_null!("a"), eq!("b", 1)).to_string(),
+            "((a IS NOT NULL) OR (b = 1))"
+        );
+    }
+
+    #[test]
+    fn test_dialect() {
+        let cond = and!(null_safe_eq!("a", "?"), eq!("b", 1));
+        assert_eq!(
+            &cond.to_string(),
+            "((a IS NOT DISTINCT FROM ?) AND (b = 1))"
+        );
+        assert_eq!(&cond.build(Some(Dialect::Mysql)), "((a <=> ?) AND (b = 1))");
+        assert_eq!(&cond.build(Some(Dialect::Sqlite)), "((a IS ?) AND (b = 1))");
+
+        // Subquery is rendered by dialect of outer query
+        let mut sub = QueryBuilder::select("tb");
+        sub.column("a_id").where_cond(null_safe_eq!("b", "?"));
+        assert_eq!(
+            &in_select!("id", sub).build(Some(Dialect::Mysql)),
+            "(id IN (SELECT a_id FROM tb WHERE (b <=> ?)))"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            &json_extract!("a", "$.b").to_string(),
+            "json_extract(a, '$.b')"
+        );
+
+        assert_eq!(
+            &gt!(json_extract_text!("a", "$.b[0]"), 1).to_string(),
+            "((a ->> '$.b[0]') > 1)"
+        );
+
+        assert_eq!(&json_contains!("a", "?").to_string(), "json_contains(a, ?)");
+
+        assert_eq!(
+            &json_contains!("a", sql_str("1"), "$.it's").to_string(),
+            "json_contains(a, '1', '$.it''s')"
+        );
+    }
+
+    #[test]
+    fn test_subquery() {
+        let sub = || {
+            let mut sub = QueryBuilder::select("tb");
+            sub.column("a_id").where_cond(gt!("b", "?"));
+            sub
+        };
+
+        assert_eq!(
+            &in_select!("id", sub()).to_string(),
+            "(id IN (SELECT a_id FROM tb WHERE (b > ?)))"
+        );
+
+        assert_eq!(
+            &exists!(QueryBuilder::select("tb")
+                .column("1")
+                .where_cond(eq!("tb.a_id", "ta.id"))
+                .clone())
+            .to_string(),
+            "(EXISTS (SELECT 1 FROM tb WHERE (tb.a_id = ta.id)))"
+        );
+
+        assert_eq!(
+            &not_exists!(sub()).to_string(),
+            "(NOT EXISTS (SELECT a_id FROM tb WHERE (b > ?)))"
+        );
+
+        assert_eq!(
+            &gt!(
+                "a",
+                subquery!(QueryBuilder::select("tb").column("b").clone())
+            )
+            .to_string(),
+            "(a > (SELECT b FROM tb))"
+        );
+
+        // Subquery with empty columns
+        let cond = and!(eq!("a", 1), exists!(QueryBuilder::select("tb")));
+        assert!(cond.validate().is_err());
+        assert!(eq!("a", 1).validate().is_ok());
+    }
+
+    #[test]
+    fn test_subquery_params() {
+        let sub_with = |b: &str| {
+            let mut sub = QueryBuilder::select("tb");
+            sub.column("a_id").where_cond(gt!("b", b));
+            sub
+        };
+        let bound = |params: Vec<i32>| {
+            let mut sub = sub_with("?");
+            sub.bind_params(params);
+            sub
+        };
+
+        // `?` of unbound subquery and string literals
+        let cond = and!(
+            or!(eq!("a", sql_str("?")), in_select!("id", sub_with("?"))),
+            and!(
+                between!("c", ["?", "?"]),
+                and!(in_select!("id", bound(vec![1])), exists!(bound(vec![2, 3])))
+            )
+        );
+        assert_eq!(
+            cond.subquery_params::<Vec<i32>>(),
+            vec![(3, &vec![1]), (3, &vec![2, 3])]
+        );
+
+        // Params of other type are not matched
+        assert!(cond.subquery_params::<Vec<u8>>().is_empty());
+        assert!(eq!("a", "?").subquery_params::<Vec<i32>>().is_empty());
     }
 }
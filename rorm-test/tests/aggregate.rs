@@ -0,0 +1,95 @@
+use rorm::{
+    query::{count, gt, max},
+    Connection, Entity, Repository, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "orders")]
+struct Order {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub user: String,
+    pub amount: i64,
+    pub discount: Option<f64>,
+}
+
+fn order_model(user: &str, amount: i64, discount: Option<f64>) -> OrderModel {
+    OrderModel {
+        user: user.into(),
+        amount: amount.into(),
+        discount: discount.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_repository() {
+    run_async_test!((repo: Repository<Order>) => {
+        assert_eq!(repo.find().count().await.unwrap(), 0);
+        assert!(!repo.find().exists().await.unwrap());
+        assert_eq!(repo.find().sum::<Option<i64>>("amount").await.unwrap(), None);
+
+        repo.insert()
+            .models([
+                order_model("alice", 10, Some(0.5)),
+                order_model("alice", 30, None),
+                order_model("bob", 20, None),
+            ])
+            .all()
+            .await
+            .unwrap();
+
+        assert_eq!(repo.find().count().await.unwrap(), 3);
+        assert!(repo.find().exists().await.unwrap());
+        assert_eq!(repo.find().sum::<i64>("amount").await.unwrap(), 60);
+        assert_eq!(repo.find().avg::<f64>("amount").await.unwrap(), 20.0);
+        assert_eq!(repo.find().min::<u8>("amount").await.unwrap(), 10);
+        assert_eq!(repo.find().max::<i64>("amount").await.unwrap(), 30);
+        assert_eq!(repo.find().max::<String>("user").await.unwrap(), "bob");
+        assert_eq!(repo.find().max::<Option<f64>>("discount").await.unwrap(), Some(0.5));
+
+        // With filter
+        let alice = || {
+            repo.find().filter_model(OrderModel {
+                user: "alice".into(),
+                ..Default::default()
+            })
+        };
+        assert_eq!(alice().count().await.unwrap(), 2);
+        assert_eq!(alice().sum::<i64>("amount").await.unwrap(), 40);
+        assert!(!repo
+            .find()
+            .filter(gt!("amount", "?"), vec![30.to_value()])
+            .exists()
+            .await
+            .unwrap());
+    });
+}
+
+#[tokio::test]
+async fn test_find_builder() {
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+    Order::init(&conn).await.unwrap();
+    Order::insert()
+        .models([order_model("alice", 10, None), order_model("bob", 20, None)])
+        .execute(&conn)
+        .await
+        .unwrap();
+
+    assert_eq!(Order::find().count(&conn).await.unwrap(), 2);
+    assert_eq!(
+        Order::find()
+            .aggregate::<u64>(&conn, count!(distinct "user"))
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        Order::find()
+            .aggregate::<i64>(&conn, max!("amount"))
+            .await
+            .unwrap(),
+        20
+    );
+}
@@ -0,0 +1,167 @@
+#[path = "codegen/entities.rs"]
+mod entities;
+
+use rorm::{
+    ColumnDesc, ColumnType, Connection, Entity, ForeignKeyDesc, IndexDesc, Migrator, TableDesc,
+};
+
+use entities::{Post, PostModel, User, UserModel};
+
+async fn setup() -> Connection {
+    env_logger::try_init().ok();
+
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+    conn.execute_many(
+        vec![
+            "CREATE TABLE user (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL DEFAULT 'NONAME' UNIQUE, age INTEGER DEFAULT 18, email TEXT)",
+            "CREATE INDEX user_index_email_age ON user (email, age)",
+            "CREATE TABLE post (id INTEGER PRIMARY KEY AUTOINCREMENT, user_id INTEGER NOT NULL REFERENCES user (id), title TEXT NOT NULL, type TEXT)",
+            "CREATE INDEX post_index_type_title ON post (type, title)",
+        ]
+        .into_iter()
+        .map(|sql| (sql.to_string(), vec![vec![]]))
+        .collect(),
+    )
+    .await
+    .unwrap();
+
+    conn
+}
+
+#[tokio::test]
+async fn test_list_foreign_keys() {
+    let conn = setup().await;
+
+    assert_eq!(conn.list_foreign_keys("user").await.unwrap(), vec![]);
+    assert_eq!(
+        conn.list_foreign_keys("post").await.unwrap(),
+        vec![ForeignKeyDesc {
+            columns: vec!["user_id".into()],
+            ref_table: "user".into(),
+            ref_columns: vec!["id".into()],
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_gen_entities() {
+    let conn = setup().await;
+
+    // Tables of migrator are skipped
+    Migrator::new().migrate_up(&conn).await.unwrap();
+    let src = rorm::gen_entities(&conn, &[]).await.unwrap();
+    assert_eq!(src, include_str!("codegen/entities.rs"));
+
+    // Index key of renamed field is its column
+    let keys = Post::INFO.indexes[0]
+        .keys
+        .iter()
+        .map(|key| key.column_name)
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["type", "title"]);
+    let desc = conn.describe_table("post").await.unwrap().unwrap();
+    assert_eq!(TableDesc::from(&Post::INFO).indexes, desc.indexes);
+
+    // Generated entities work with the table
+    let user_id = conn
+        .repository::<User>()
+        .insert()
+        .model(UserModel {
+            email: Some("bob@example.com").into(),
+            ..Default::default()
+        })
+        .one()
+        .await
+        .unwrap();
+    conn.repository::<Post>()
+        .insert()
+        .model(PostModel {
+            user_id: (user_id as i64).into(),
+            title: "hello".into(),
+            type_: Some("note").into(),
+            ..Default::default()
+        })
+        .one()
+        .await
+        .unwrap();
+
+    let user = conn
+        .repository::<User>()
+        .find()
+        .filter_model(user_id as i64)
+        .one()
+        .await
+        .unwrap();
+    assert_eq!(user.name, "NONAME");
+    assert_eq!(user.age, Some(18));
+    assert_eq!(user.posts.len(), 1);
+    assert_eq!(user.posts[0].title, "hello");
+    assert_eq!(user.posts[0].type_.as_deref(), Some("note"));
+}
+
+#[test]
+fn test_gen_entities_from_desc() {
+    let column = |name: &str, ty: ColumnType| ColumnDesc {
+        name: name.into(),
+        ty,
+        is_primary_key: false,
+        is_not_null: true,
+        is_auto_increment: false,
+        default: None,
+        is_unique: false,
+    };
+    let schema = vec![(
+        TableDesc {
+            name: "tree_node".into(),
+            columns: vec![
+                ColumnDesc {
+                    is_primary_key: true,
+                    ..column("id", ColumnType::U32)
+                },
+                ColumnDesc {
+                    is_not_null: false,
+                    ..column("parent_id", ColumnType::U32)
+                },
+                ColumnDesc {
+                    default: Some("'it''s'".into()),
+                    ..column("name", ColumnType::Str(20))
+                },
+                ColumnDesc {
+                    default: Some("CURRENT_TIMESTAMP".into()),
+                    ..column("type", ColumnType::Bytes(16))
+                },
+            ],
+            indexes: vec![IndexDesc {
+                name: "tree_node_unique".into(),
+                keys: vec!["parent_id".into(), "name".into()],
+                is_unique: true,
+            }],
+        },
+        vec![ForeignKeyDesc {
+            columns: vec!["parent_id".into()],
+            ref_table: "tree_node".into(),
+            ref_columns: vec!["id".into()],
+        }],
+    )];
+
+    assert_eq!(
+        rorm::gen_entities_from_desc(&schema),
+        r#"use rorm::Entity;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "tree_node")]
+#[rorm(index = [parent_id, name])] // FIXME: `tree_node_unique` is unique
+pub struct TreeNode {
+    #[rorm(primary_key)]
+    pub id: u32,
+    pub parent_id: Option<u32>,
+    #[rorm(length = 20, default = "it''s")]
+    pub name: String,
+    // FIXME: Default `CURRENT_TIMESTAMP` is not a literal
+    #[rorm(column = "type", length = 16)]
+    pub type_: Vec<u8>,
+    // Relation of `parent_id` to self is skipped, loading it would recurse
+}
+"#
+    );
+}
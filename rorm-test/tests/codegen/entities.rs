@@ -0,0 +1,28 @@
+use rorm::Entity;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "post")]
+#[rorm(index = [type_, title])]
+pub struct Post {
+    #[rorm(primary_key, auto_increment)]
+    pub id: i64,
+    pub user_id: i64,
+    pub title: String,
+    #[rorm(column = "type")]
+    pub type_: Option<String>,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+#[rorm(index = [email, age])]
+pub struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: i64,
+    #[rorm(default = "NONAME", unique)]
+    pub name: String,
+    #[rorm(default = 18)]
+    pub age: Option<i64>,
+    pub email: Option<String>,
+    #[rorm(relation = id > user_id)]
+    pub posts: Vec<Post>,
+}
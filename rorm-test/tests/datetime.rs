@@ -0,0 +1,148 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use rorm::{Date, DateTime, Entity, FromValue, Repository, Time, Timestamp, ToValue};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "event")]
+struct Event {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub day: NaiveDate,
+    pub time: Option<chrono::NaiveTime>,
+    pub start: NaiveDateTime,
+    pub created_at: chrono::DateTime<Utc>,
+    pub deleted_at: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "log")]
+struct Log {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub day: time::Date,
+    pub start: Option<time::PrimitiveDateTime>,
+    pub created_at: time::OffsetDateTime,
+    pub updated_at: Timestamp,
+}
+
+#[tokio::test]
+async fn test_chrono() {
+    run_async_test!((repo: Repository<Event>) => {
+        let day = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+        let start = day.and_hms_milli_opt(8, 30, 0, 250).unwrap();
+        let created_at = Utc.timestamp_opt(1645000000, 0).single().unwrap();
+
+        let id = repo
+            .insert()
+            .model(EventModel {
+                day: day.into(),
+                time: None.into(),
+                start: start.into(),
+                created_at: created_at.into(),
+                deleted_at: None.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        let event = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(event.day, day);
+        assert_eq!(event.time, None);
+        assert_eq!(event.start, start);
+        assert_eq!(event.created_at, created_at);
+        assert_eq!(event.deleted_at, None);
+
+        // Stored as text and unix seconds
+        let (day, start, created_at) = repo
+            .conn
+            .query_one_map("SELECT day, start, created_at FROM event", vec![], |row| async move {
+                Ok((
+                    row.get::<String>("day")?,
+                    row.get::<String>("start")?,
+                    row.get::<i64>("created_at")?,
+                ))
+            })
+            .await
+            .unwrap();
+        assert_eq!(day, "2022-02-28");
+        assert_eq!(start, "2022-02-28 08:30:00.25");
+        assert_eq!(created_at, 1645000000);
+
+        // Text compares in time order
+        let events = repo
+            .conn
+            .query_many_map(
+                "SELECT id FROM event WHERE start > ?",
+                vec![NaiveDate::from_ymd_opt(2022, 2, 28).unwrap().and_hms_opt(8, 0, 0).unwrap().to_value()],
+                |row| async move { row.get::<u32>("id") },
+            )
+            .await
+            .unwrap();
+        assert_eq!(events, vec![id]);
+    });
+}
+
+#[tokio::test]
+async fn test_time() {
+    run_async_test!((repo: Repository<Log>) => {
+        let day = time::Date::from_calendar_date(1969, time::Month::December, 31).unwrap();
+        let created_at = time::OffsetDateTime::from_unix_timestamp(-1).unwrap();
+
+        let id = repo
+            .insert()
+            .model(LogModel {
+                day: day.into(),
+                start: None.into(),
+                created_at: created_at.into(),
+                updated_at: Timestamp::new(86400, 0).unwrap().into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        let log = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(log.day, day);
+        assert_eq!(log.start, None);
+        assert_eq!(log.created_at, created_at);
+        assert_eq!(log.updated_at.to_datetime().to_string(), "1970-01-02 00:00:00");
+    });
+}
+
+#[test]
+fn test_convert() {
+    let date = Date::new(2000, 2, 29).unwrap();
+    assert_eq!(Date::from_days(date.to_days()), date);
+    assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+    assert_eq!(Date::from_days(-1).to_string(), "1969-12-31");
+    assert!(Date::new(2100, 2, 29).is_err());
+
+    assert_eq!(
+        "2022-01-01T08:00:00.5".parse::<DateTime>().unwrap(),
+        DateTime::new(
+            Date::new(2022, 1, 1).unwrap(),
+            Time::new(8, 0, 0, 500_000_000).unwrap()
+        )
+    );
+    assert!("2022-01-01 24:00:00".parse::<DateTime>().is_err());
+    assert!("12:00".parse::<Time>().is_err());
+
+    // Offset is converted to UTC
+    let ts = "2022-01-01 08:00:00+08:00".parse::<Timestamp>().unwrap();
+    assert_eq!(ts, "2022-01-01T00:00:00Z".parse::<Timestamp>().unwrap());
+    assert_eq!(ts.to_string(), "2022-01-01 00:00:00");
+
+    // Date time is read from mysql text protocol as bytes
+    let v = rorm::Value::Bytes(b"2022-01-01 00:00:00".to_vec());
+    assert_eq!(Timestamp::from_value(&v).unwrap(), ts);
+    assert_eq!(
+        Date::from_value(&v).unwrap(),
+        Date::new(2022, 1, 1).unwrap()
+    );
+
+    // Unix seconds out of i64 fail instead of wrapping
+    let v = rorm::Value::U64(1_640_995_200);
+    assert_eq!(Timestamp::from_value(&v).unwrap(), ts);
+    assert!(Timestamp::from_value(&rorm::Value::U64(u64::MAX)).is_err());
+}
@@ -0,0 +1,118 @@
+use rorm::{query::eq, ColumnType, DbEnum, DbType, Entity, Repository, ToValue, Value};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Clone, Copy, PartialEq, DbEnum)]
+#[rorm(check)]
+enum Status {
+    #[rorm(rename = "active")]
+    Active,
+    #[rorm(rename = "banned")]
+    Banned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, DbEnum)]
+#[rorm(repr = u8)]
+enum Level {
+    Low = 1,
+    Middle,
+    High = 10,
+}
+
+#[derive(Debug, PartialEq, DbEnum)]
+#[rorm(length = 10)]
+enum Role {
+    Admin,
+    Guest,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    #[rorm(default = "active")]
+    pub status: Status,
+    pub level: Level,
+    pub role: Option<Role>,
+}
+
+#[tokio::test]
+async fn test_db_enum() {
+    run_async_test!((repo: Repository<User>) => {
+        let id = repo
+            .insert()
+            .model(UserModel {
+                level: Level::Middle.into(),
+                role: Some(Role::Admin).into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+        repo.insert()
+            .model(UserModel {
+                status: Status::Banned.into(),
+                level: Level::High.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        let user = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(user.status, Status::Active);
+        assert_eq!(user.level, Level::Middle);
+        assert_eq!(user.role, Some(Role::Admin));
+
+        let users = repo
+            .find()
+            .filter(eq!("level", "?"), vec![Level::High.to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].status, Status::Banned);
+        assert_eq!(users[0].role, None);
+
+        // Stored values
+        let (status, level) = repo
+            .conn
+            .query_one_map("SELECT status, level FROM user WHERE id = ?", vec![id.to_value()], |row| async move {
+                Ok((row.get::<String>("status")?, row.get::<i64>("level")?))
+            })
+            .await
+            .unwrap();
+        assert_eq!((status.as_str(), level), ("active", 2));
+
+        // Rejected by check constraint
+        assert!(repo
+            .conn
+            .execute_one("UPDATE user SET status = 'unknown' WHERE id = ?", vec![id.to_value()])
+            .await
+            .is_err());
+
+        // Unknown value can't be read
+        repo.conn
+            .execute_one("UPDATE user SET level = 3 WHERE id = ?", vec![id.to_value()])
+            .await
+            .unwrap();
+        assert!(repo.find().filter_model(id).one().await.is_err());
+
+        let desc = repo.conn.describe_table("user").await.unwrap().unwrap();
+        assert!(repo.conn.diff_table(&desc).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_column_type() {
+    assert_eq!(
+        User::INFO.columns[1].ty,
+        ColumnType::Enum(vec!["active".into(), "banned".into()].into())
+    );
+    assert_eq!(User::INFO.columns[2].ty, ColumnType::U8);
+    assert_eq!(User::INFO.columns[3].ty, ColumnType::Str(10));
+    assert!(!User::INFO.columns[3].is_not_null);
+    assert_eq!(<Role as DbType>::COLUMN_TYPE, ColumnType::Str(10));
+    assert_eq!(<Role as DbEnum>::COLUMN_TYPE, ColumnType::Str(10));
+    assert!(matches!(Role::Guest.to_value(), Value::Str(s) if s == "Guest"));
+}
@@ -0,0 +1,76 @@
+use rorm::{ColumnType, DbType, Entity, Repository};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Clone, Copy, PartialEq, DbType)]
+pub struct UserId(u32);
+
+#[derive(Debug, Clone, PartialEq, DbType)]
+pub struct Email(String);
+
+type Name = std::string::String;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: UserId,
+    #[rorm(length = 50)]
+    pub name: Name,
+    pub nickname: std::option::Option<std::string::String>,
+    #[rorm(length = 100)]
+    pub email: Option<Email>,
+    pub invited_by: Option<UserId>,
+}
+
+#[tokio::test]
+async fn test_db_type() {
+    run_async_test!((repo: Repository<User>) => {
+        let bob = repo
+            .insert()
+            .model(UserModel {
+                name: "bob".to_string().into(),
+                email: Some(Email("bob@example.com".into())).into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+        assert_eq!(bob, UserId(1));
+
+        let alice = repo
+            .insert()
+            .model(UserModel {
+                name: "alice".to_string().into(),
+                invited_by: Some(bob).into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        let user = repo.find().filter_model(alice).one().await.unwrap();
+        assert_eq!(user.id, UserId(2));
+        assert_eq!(user.invited_by, Some(bob));
+        assert_eq!(user.email, None);
+
+        let user = repo.find().filter_model(bob).one().await.unwrap();
+        assert_eq!(user.email, Some(Email("bob@example.com".into())));
+
+        let desc = repo.conn.describe_table("user").await.unwrap().unwrap();
+        assert!(repo.conn.diff_table(&desc).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_column_type() {
+    let columns = User::INFO.columns;
+    assert_eq!(columns[0].ty, ColumnType::U32);
+    assert_eq!(columns[1].ty, ColumnType::Str(50));
+    assert!(columns[1].is_not_null);
+    assert_eq!(columns[2].ty, ColumnType::Str(65535));
+    assert!(!columns[2].is_not_null);
+    assert_eq!(columns[3].ty, ColumnType::Str(100));
+    assert!(!columns[3].is_not_null);
+    assert_eq!(columns[4].ty, ColumnType::U32);
+    assert!(!columns[4].is_not_null);
+}
@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use rorm::{
+    query::{gt, lt},
+    Decimal, Entity, FromValue, Repository, ToValue, Value,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "product")]
+struct Product {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    #[rorm(precision = 10, scale = 2)]
+    pub price: rust_decimal::Decimal,
+    pub discount: Option<Decimal>,
+}
+
+#[tokio::test]
+async fn test_decimal() {
+    run_async_test!((repo: Repository<Product>) => {
+        // Not representable by f64
+        let price = rust_decimal::Decimal::from_str("12345678.91").unwrap();
+        let discount = "0.10000000000000000001".parse::<Decimal>().unwrap();
+
+        let id = repo
+            .insert()
+            .model(ProductModel {
+                price: price.into(),
+                discount: Some(discount).into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        let product = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(product.price, price);
+        assert_eq!(product.discount, Some(discount));
+
+        // Stored as text in sqlite
+        let text = repo
+            .conn
+            .query_one_map("SELECT discount FROM product", vec![], |row| async move {
+                row.get::<String>("discount")
+            })
+            .await
+            .unwrap();
+        assert_eq!(text, "0.10000000000000000001");
+
+        let desc = repo.conn.describe_table("product").await.unwrap().unwrap();
+        assert!(repo.conn.diff_table(&desc).await.unwrap().is_empty());
+        assert_eq!(Product::INFO.columns[1].ty, rorm::ColumnType::Decimal(10, 2));
+    });
+}
+
+#[tokio::test]
+async fn test_order() {
+    run_async_test!((repo: Repository<Product>) => {
+        for price in ["9.00", "10.00", "100.50"] {
+            repo.insert()
+                .model(ProductModel {
+                    price: rust_decimal::Decimal::from_str(price).unwrap().into(),
+                    ..Default::default()
+                })
+                .one()
+                .await
+                .unwrap();
+        }
+        let prices = |products: Vec<Product>| {
+            products
+                .into_iter()
+                .map(|p| p.price.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Compared by value, not by text
+        let products = repo.find().order_by("price", true).all().await.unwrap();
+        assert_eq!(prices(products), vec!["9", "10", "100.5"]);
+
+        let param = Decimal::from_str("9.50").unwrap().to_value();
+        let products = repo
+            .find()
+            .filter(gt!("price", "?"), vec![param.clone()])
+            .order_by("price", false)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(prices(products), vec!["100.5", "10"]);
+
+        let products = repo.find().filter(lt!("price", "?"), vec![param]).all().await.unwrap();
+        assert_eq!(prices(products), vec!["9"]);
+
+        let min = repo.find().min::<Decimal>("price").await.unwrap();
+        assert_eq!(min, Decimal::new(9, 0));
+        let max = repo.find().max::<Decimal>("price").await.unwrap();
+        assert_eq!(max, Decimal::new(10050, 2));
+    });
+}
+
+#[test]
+fn test_parse() {
+    let v = "-0.050".parse::<Decimal>().unwrap();
+    assert_eq!(v, Decimal::new(-50, 3));
+    assert_eq!(v.to_string(), "-0.050");
+    assert_eq!(v, Decimal::new(-5, 2));
+    assert_eq!(v.normalize().to_string(), "-0.05");
+    assert_eq!(Decimal::new(123, 0).to_string(), "123");
+
+    assert!("1.2.3".parse::<Decimal>().is_err());
+    assert!(".".parse::<Decimal>().is_err());
+    assert!("1e5".parse::<Decimal>().is_err());
+    assert!("1".repeat(40).parse::<Decimal>().is_err());
+
+    // Ordered by value
+    assert!(Decimal::new(900, 2) < Decimal::new(1000, 2));
+    assert!(Decimal::new(-1, 0) < Decimal::new(-5, 1));
+    assert!(Decimal::new(i128::MAX, 0) > Decimal::new(1, 30));
+    assert!(Decimal::new(i128::MIN, 0) < Decimal::new(-1, 30));
+    // Scale difference overflows i128
+    assert!(Decimal::new(0, 0) > Decimal::new(-1, 40));
+    assert!(Decimal::new(0, 0) < Decimal::new(1, 40));
+    assert!(Decimal::new(-1, 0) < Decimal::new(1, 40));
+    assert!(Decimal::new(1, 0) > Decimal::new(1, 40));
+    assert_eq!(
+        Decimal::new(120, 2).cmp(&Decimal::new(12, 1)),
+        std::cmp::Ordering::Equal
+    );
+
+    // Mysql returns decimal as bytes
+    let v = Value::Bytes(b"99.90".to_vec());
+    assert_eq!(Decimal::from_value(&v).unwrap(), Decimal::new(999, 1));
+    assert_eq!(
+        rust_decimal::Decimal::from_value(&v).unwrap(),
+        rust_decimal::Decimal::new(999, 1)
+    );
+    assert_eq!(
+        Decimal::from_value(&Value::F64(0.1)).unwrap(),
+        Decimal::new(1, 1)
+    );
+}
@@ -0,0 +1,226 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rorm::{
+    query::{
+        abs, cast, coalesce, col, date, day, eq, gt, length, lower, month, sql_str, sum, upper,
+        year, CastType, Expr,
+    },
+    Date, DateTime, Decimal, Entity, Repository, Time, Timestamp, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "item")]
+struct Item {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    pub nickname: Option<String>,
+    pub price: i64,
+    pub count: u32,
+    pub created_at: NaiveDateTime,
+}
+
+fn datetime(y: i32, m: u32, d: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_opt(12, 30, 0)
+        .unwrap()
+}
+
+fn item_model(name: &str, price: i64, count: u32, at: NaiveDateTime) -> ItemModel {
+    ItemModel {
+        name: name.into(),
+        price: price.into(),
+        count: count.into(),
+        created_at: at.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_expr() {
+    run_async_test!((repo: Repository<Item>) => {
+        repo.insert()
+            .models([
+                item_model("Pen", 3, 10, datetime(2021, 1, 15)),
+                item_model("Book", 20, 2, datetime(2021, 3, 1)),
+                item_model("Lamp", 45, 1, datetime(2022, 3, 20)),
+            ])
+            .all()
+            .await
+            .unwrap();
+
+        let names = |items: Vec<Item>| items.into_iter().map(|v| v.name).collect::<Vec<_>>();
+
+        // Arithmetic in where and order by
+        let items = repo
+            .find()
+            .filter(gt!(col("price") * col("count"), "?"), vec![25.to_value()])
+            .order_by(-(col("price") * col("count")), true)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(items), vec!["Lamp", "Book", "Pen"]);
+
+        // Functions in select columns
+        let rows: Vec<(String, String, u32, i64, String)> = repo
+            .find()
+            .filter(eq!("name", sql_str("Pen")), vec![])
+            .select([
+                lower!("name"),
+                upper!("name"),
+                length!("name"),
+                abs!(col("count") - 20),
+                coalesce!("nickname", "name", sql_str("-")),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![("pen".into(), "PEN".into(), 3, 10, "Pen".into())]);
+
+        // Date functions
+        let rows: Vec<(String, u32, u32, u32)> = repo
+            .find()
+            .filter(eq!(year!("created_at"), 2021), vec![])
+            .order_by("id", true)
+            .select([
+                date!("created_at"),
+                year!("created_at"),
+                month!("created_at"),
+                day!("created_at"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![("2021-01-15".into(), 2021, 1, 15), ("2021-03-01".into(), 2021, 3, 1)]
+        );
+
+        // Group by expression
+        let rows: Vec<(u32, i64)> = repo
+            .find()
+            .group_by(year!("created_at"))
+            .order_by(year!("created_at"), true)
+            .select([year!("created_at"), sum!("price").into()])
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![(2021, 23), (2022, 45)]);
+
+        // Case when and cast
+        let level = Expr::case_when(gt!("price", 40), sql_str("high"))
+            .when(gt!("price", 10), sql_str("medium"))
+            .otherwise(sql_str("low"));
+        let rows: Vec<(String, String, i64)> = repo
+            .find()
+            .order_by("id", true)
+            .select([
+                col("name"),
+                level.alias("level"),
+                cast!(col("price") / 2, CastType::Signed).alias("half"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("Pen".into(), "low".into(), 1),
+                ("Book".into(), "medium".into(), 10),
+                ("Lamp".into(), "high".into(), 22),
+            ]
+        );
+
+        // Division is not truncated, cast to decimal is rounded to scale
+        let rows: Vec<(f64, Decimal)> = repo
+            .find()
+            .order_by("id", true)
+            .select([col("price") / 2, cast!(col("price") / 3, CastType::Decimal(10, 2))])
+            .await
+            .unwrap();
+        let decimal = |s: &str| s.parse::<Decimal>().unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1.5, decimal("1")),
+                (10.0, decimal("6.67")),
+                (22.5, decimal("15")),
+            ]
+        );
+
+        // Update by expression
+        repo.update()
+            .set("price", (col("price") * 2).into(), vec![])
+            .filter(eq!("name", sql_str("Pen")), vec![])
+            .all()
+            .await
+            .unwrap();
+        let items = repo.find().filter(eq!("price", 6), vec![]).all().await.unwrap();
+        assert_eq!(names(items), vec!["Pen"]);
+    });
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "event")]
+struct Event {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    pub at: Timestamp,
+}
+
+fn timestamp(y: i32, m: u8, d: u8) -> Timestamp {
+    let date = Date::new(y, m, d).unwrap();
+    let time = Time::new(23, 59, 59, 0).unwrap();
+
+    Timestamp::from_datetime(&DateTime::new(date, time))
+}
+
+#[tokio::test]
+async fn test_timestamp() {
+    run_async_test!((repo: Repository<Event>) => {
+        for (name, at) in [
+            ("launch", timestamp(1969, 7, 20)),
+            ("release", timestamp(2021, 1, 15)),
+            ("patch", timestamp(2021, 3, 1)),
+            ("major", timestamp(2022, 12, 31)),
+        ] {
+            repo.insert()
+                .model(EventModel { name: name.into(), at: at.into(), ..Default::default() })
+                .one()
+                .await
+                .unwrap();
+        }
+
+        // Date functions on timestamp
+        let rows: Vec<(String, String, u32, u32, u32)> = repo
+            .find()
+            .filter(eq!(year!("at"), "?"), vec![2021.to_value()])
+            .order_by(day!("at"), false)
+            .select([col("name"), date!("at"), year!("at"), month!("at"), day!("at")])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("release".into(), "2021-01-15".into(), 2021, 1, 15),
+                ("patch".into(), "2021-03-01".into(), 2021, 3, 1),
+            ]
+        );
+
+        let rows: Vec<(String,)> = repo
+            .find()
+            .filter(eq!(date!("at"), sql_str("1969-07-20")), vec![])
+            .select(["name"])
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![("launch".to_string(),)]);
+
+        // Group by year of timestamp
+        let rows: Vec<(i32, u32)> = repo
+            .find()
+            .group_by(year!("at"))
+            .order_by(year!("at"), true)
+            .select([year!("at"), Expr::from(rorm::query::count!())])
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![(1969, 1), (2021, 2), (2022, 1)]);
+    });
+}
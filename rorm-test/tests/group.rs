@@ -0,0 +1,168 @@
+use rorm::{
+    error::Result,
+    query::{alias, count, eq, gt, sum},
+    Entity, FromRow, Repository, Row, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "orders")]
+struct Order {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub user_id: u32,
+    pub city: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, PartialEq)]
+struct CityTotal {
+    city: String,
+    orders: u32,
+    total: i64,
+}
+
+impl FromRow for CityTotal {
+    fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            city: row.get("city")?,
+            orders: row.get("orders")?,
+            total: row.get("total")?,
+        })
+    }
+}
+
+fn order_model(user_id: u32, city: &str, amount: i64) -> OrderModel {
+    OrderModel {
+        user_id: user_id.into(),
+        city: city.into(),
+        amount: amount.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_group() {
+    run_async_test!((user_repo: Repository<User>, order_repo: Repository<Order>) => {
+        let alice = user_repo
+            .insert()
+            .model(UserModel { name: "alice".into(), ..Default::default() })
+            .one()
+            .await
+            .unwrap();
+        let bob = user_repo
+            .insert()
+            .model(UserModel { name: "bob".into(), ..Default::default() })
+            .one()
+            .await
+            .unwrap();
+        order_repo
+            .insert()
+            .models([
+                order_model(alice, "paris", 10),
+                order_model(alice, "paris", 20),
+                order_model(bob, "paris", 5),
+                order_model(bob, "rome", 7),
+            ])
+            .all()
+            .await
+            .unwrap();
+
+        // Tuple by position
+        let rows: Vec<(String, u32, i64)> = order_repo
+            .find()
+            .group_by("city")
+            .order_by("city", true)
+            .select(["city", "COUNT(*)", "SUM(amount)"])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![("paris".to_string(), 3, 35), ("rome".to_string(), 1, 7)]
+        );
+
+        // Struct by alias, where params are placed before having params
+        let rows: Vec<CityTotal> = order_repo
+            .find()
+            .filter(gt!("amount", "?"), vec![6.to_value()])
+            .group_by("city")
+            .having(gt!(sum!("amount"), "?"), vec![10.to_value()])
+            .select([
+                "city".to_string(),
+                alias(count!(), "orders"),
+                alias(sum!("amount"), "total"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![CityTotal {
+                city: "paris".into(),
+                orders: 2,
+                total: 30,
+            }]
+        );
+
+        // Distinct
+        let cities: Vec<(String,)> = order_repo
+            .find()
+            .distinct()
+            .order_by("city", false)
+            .select(["city"])
+            .await
+            .unwrap();
+        assert_eq!(cities, vec![("rome".to_string(),), ("paris".to_string(),)]);
+
+        // Distinct entities over joined rows
+        let users = user_repo
+            .find()
+            .inner_join("orders", eq!("orders.user_id", "user.id"), vec![])
+            .filter(eq!("orders.city", "'paris'"), vec![])
+            .distinct()
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 2);
+
+        // Count of groups, distinct rows and entities over joined rows
+        let count = order_repo.find().group_by("city").count().await.unwrap();
+        assert_eq!(count, 2);
+
+        let count = order_repo
+            .find()
+            .group_by("city")
+            .having(gt!(count!(), "?"), vec![1.to_value()])
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let count = order_repo
+            .find()
+            .filter(eq!("city", "?"), vec!["paris".to_string().to_value()])
+            .group_by("user_id")
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let count = user_repo
+            .find()
+            .inner_join("orders", eq!("orders.user_id", "user.id"), vec![])
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        assert_eq!(order_repo.find().distinct().count().await.unwrap(), 4);
+        assert_eq!(order_repo.find().count().await.unwrap(), 4);
+    });
+}
@@ -0,0 +1,123 @@
+use rorm::{
+    in_list,
+    query::{and, gt},
+    Entity, Repository, ToValue, IN_LIST_CHUNK_SIZE,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "item")]
+struct Item {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub price: u32,
+}
+
+#[test]
+fn test_in_list() {
+    let (cond, params) = in_list("id", vec![1u32, 2, 3]);
+    assert_eq!(cond.to_string(), "(id IN (?, ?, ?))");
+    assert_eq!(params.len(), 3);
+
+    let (cond, params) = in_list("id", Vec::<u32>::new());
+    assert_eq!(cond.to_string(), "(1 = 0)");
+    assert!(params.is_empty());
+}
+
+#[tokio::test]
+async fn test_filter_in() {
+    run_async_test!((repo: Repository<Item>) => {
+        let count = IN_LIST_CHUNK_SIZE as u32 * 2 + 10;
+        let models = (0..count)
+            .map(|i| ItemModel {
+                price: (i % 10).into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        repo.insert().models(models).all().await.unwrap();
+
+        let ids = |items: Vec<Item>| items.into_iter().map(|i| i.id).collect::<Vec<_>>();
+
+        let items = repo.find().filter_in("id", vec![3u32, 1, 100]).all().await.unwrap();
+        assert_eq!(ids(items), vec![1, 3, 100]);
+
+        // Empty list matches nothing
+        let items = repo.find().filter_in("id", Vec::<u32>::new()).all().await.unwrap();
+        assert!(items.is_empty());
+
+        // ANDed with filter, filter params are placed first
+        let items = repo
+            .find()
+            .filter(gt!("price", "?"), vec![4.to_value()])
+            .filter_in("id", 1..=10u32)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(ids(items), vec![6, 7, 8, 9, 10]);
+
+        // Long list is split into multiple queries
+        let all_ids = (1..=count).collect::<Vec<_>>();
+        let items = repo.find().filter_in("id", all_ids.clone()).all().await.unwrap();
+        assert_eq!(items.len(), count as usize);
+        assert_eq!(repo.find().filter_in("id", all_ids.clone()).count().await.unwrap(), count as u64);
+        assert!(repo.find().filter_in("id", all_ids.clone()).exists().await.unwrap());
+
+        // Duplicate values in different chunks match the row once
+        let dup_ids = (1..=count).chain([1, count]).collect::<Vec<_>>();
+        let items = repo.find().filter_in("id", dup_ids.clone()).all().await.unwrap();
+        assert_eq!(items.len(), count as usize);
+        assert_eq!(repo.find().filter_in("id", dup_ids).count().await.unwrap(), count as u64);
+        assert!(repo
+            .find()
+            .filter_in("id", all_ids.clone())
+            .sum::<i64>("price")
+            .await
+            .is_err());
+
+        // Clauses can't be applied to each query of long list
+        assert!(repo
+            .find()
+            .filter_in("id", all_ids.clone())
+            .order_by("id", false)
+            .limit(10, 0)
+            .await
+            .is_err());
+        assert!(repo.find().filter_in("id", all_ids.clone()).limit(10, 0).await.is_err());
+        assert!(repo.find().filter_in("id", all_ids.clone()).order_by("id", true).all().await.is_err());
+        assert!(repo.find().filter_in("id", all_ids.clone()).distinct().all().await.is_err());
+        assert!(repo
+            .find()
+            .filter_in("id", all_ids.clone())
+            .group_by("price")
+            .count()
+            .await
+            .is_err());
+        assert!(repo
+            .find()
+            .filter_in("id", all_ids.clone())
+            .select::<(u32,), _, _>(["price"])
+            .await
+            .is_err());
+
+        // But can be applied to list in one query
+        let items = repo
+            .find()
+            .filter_in("id", all_ids[..IN_LIST_CHUNK_SIZE].to_vec())
+            .order_by("id", false)
+            .limit(3, 0)
+            .await
+            .unwrap();
+        let last = IN_LIST_CHUNK_SIZE as u32;
+        assert_eq!(ids(items), vec![last, last - 1, last - 2]);
+
+        // Bound by filter directly
+        let (cond, params) = in_list("id", vec![1u32, 2, 3]);
+        let items = repo
+            .find()
+            .filter(and!(cond, gt!("price", "?")), [params, vec![1.to_value()]].concat())
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(ids(items), vec![3]);
+    });
+}
@@ -0,0 +1,169 @@
+use rorm::{
+    query::{alias, and, eq, is_null, like, JoinKind, QueryBuilder},
+    Entity, Repository, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    pub invited_by: Option<u32>,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "post")]
+struct Post {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub user_id: u32,
+    pub title: String,
+}
+
+fn user_model(name: &str, invited_by: Option<u32>) -> UserModel {
+    UserModel {
+        name: name.into(),
+        invited_by: invited_by.into(),
+        ..Default::default()
+    }
+}
+
+fn post_model(user_id: u32, title: &str) -> PostModel {
+    PostModel {
+        user_id: user_id.into(),
+        title: title.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_build() {
+    let sql = QueryBuilder::select(&alias("user", "u"))
+        .columns(["u.name", "p.title"])
+        .inner_join(alias("post", "p"), eq!("p.user_id", "u.id"))
+        .left_join(
+            "avatar",
+            and!(eq!("avatar.user_id", "u.id"), eq!("avatar.size", "?")),
+        )
+        .where_cond(like!("p.title", "?"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT u.name, p.title FROM user AS u INNER JOIN post AS p ON (p.user_id = u.id) \
+         LEFT JOIN avatar ON ((avatar.user_id = u.id) AND (avatar.size = ?)) WHERE (p.title LIKE ?)"
+    );
+
+    // On condition is required except cross join
+    assert!(QueryBuilder::select("user")
+        .column("*")
+        .join(JoinKind::Inner, "post", None)
+        .build()
+        .is_err());
+    assert!(QueryBuilder::select("user")
+        .column("*")
+        .join(
+            JoinKind::Cross,
+            "post",
+            Some(eq!("post.user_id", "user.id"))
+        )
+        .build()
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_find() {
+    run_async_test!((user_repo: Repository<User>, post_repo: Repository<Post>) => {
+        let alice = user_repo.insert().model(user_model("alice", None)).one().await.unwrap();
+        let bob = user_repo.insert().model(user_model("bob", Some(alice))).one().await.unwrap();
+        let carol = user_repo.insert().model(user_model("carol", Some(bob))).one().await.unwrap();
+        post_repo
+            .insert()
+            .models([post_model(alice, "rust"), post_model(carol, "go")])
+            .all()
+            .await
+            .unwrap();
+
+        let names = |users: Vec<User>| users.into_iter().map(|u| u.name).collect::<Vec<_>>();
+
+        // Filter by columns of joined table, `id` of post doesn't override `id` of user
+        let users = user_repo
+            .find()
+            .inner_join("post", eq!("post.user_id", "user.id"), vec![])
+            .filter(eq!("post.title", "?"), vec!["go".to_string().to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, carol);
+        assert_eq!(users[0].name, "carol");
+
+        // Join params are placed before where params
+        let users = user_repo
+            .find()
+            .filter_model(UserModel {
+                invited_by: Some(bob).into(),
+                ..Default::default()
+            })
+            .inner_join(
+                "post",
+                and!(eq!("post.user_id", "user.id"), eq!("post.title", "?")),
+                vec!["go".to_string().to_value()],
+            )
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["carol"]);
+
+        // Self join by alias
+        let users = user_repo
+            .find()
+            .inner_join(alias("user", "inviter"), eq!("inviter.id", "user.invited_by"), vec![])
+            .filter(eq!("inviter.name", "?"), vec!["alice".to_string().to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        // Users without posts
+        let users = user_repo
+            .find()
+            .left_join("post", eq!("post.user_id", "user.id"), vec![])
+            .filter(is_null!("post.id"), vec![])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        // Entity matching multiple joined rows is returned once
+        post_repo.insert().model(post_model(alice, "go")).one().await.unwrap();
+        let users = user_repo
+            .find()
+            .inner_join("post", eq!("post.user_id", "user.id"), vec![])
+            .order_by("user.id", true)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["alice", "carol"]);
+
+        let users = user_repo
+            .find()
+            .inner_join("post", eq!("post.user_id", "user.id"), vec![])
+            .filter(eq!("post.title", "?"), vec!["go".to_string().to_value()])
+            .order_by("user.id", true)
+            .limit(2, 0)
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["alice", "carol"]);
+
+        let count = user_repo
+            .find()
+            .inner_join("post", eq!("post.user_id", "user.id"), vec![])
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    });
+}
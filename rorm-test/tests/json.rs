@@ -0,0 +1,142 @@
+use rorm::{
+    query::{and, eq, gt, json_contains, json_extract, json_extract_text},
+    ColumnType, Entity, Repository, ToValue,
+};
+use rorm_test::run_async_test;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    #[rorm(serde = json_column)]
+    pub profile: Profile,
+    pub extra: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct LegacyUser {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    #[rorm(serde = serde_json)]
+    pub profile: Profile,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub age: u32,
+    pub tags: Vec<String>,
+}
+
+fn user_model(name: &str, age: u32, tags: &[&str]) -> UserModel {
+    UserModel {
+        name: name.into(),
+        profile: Profile {
+            age,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+        .into(),
+        extra: Some(json!({ "level": age / 10 })).into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_json() {
+    run_async_test!((repo: Repository<User>) => {
+        repo.insert()
+            .models([
+                user_model("bob", 30, &["rust", "go"]),
+                user_model("alice", 20, &["rust"]),
+                user_model("carol", 40, &["c"]),
+            ])
+            .all()
+            .await
+            .unwrap();
+
+        let names = |users: Vec<User>| users.into_iter().map(|u| u.name).collect::<Vec<_>>();
+
+        // Filter and order by json fields
+        let users = repo
+            .find()
+            .filter(gt!(json_extract!("profile", "$.age"), "?"), vec![25.to_value()])
+            .order_by(json_extract!("profile", "$.age"), false)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["carol", "bob"]);
+
+        // Containment
+        let users = repo
+            .find()
+            .filter(
+                json_contains!("profile", "?", "$.tags"),
+                vec![json!("rust").to_string().to_value()],
+            )
+            .order_by("id", true)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob", "alice"]);
+
+        let users = repo
+            .find()
+            .filter(
+                and!(
+                    json_contains!("profile", "?"),
+                    eq!(json_extract_text!("extra", "$.level"), 3)
+                ),
+                vec![json!({ "tags": ["go"] }).to_string().to_value()],
+            )
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        let bob = repo.find().filter_model(1).one().await.unwrap();
+        assert_eq!(bob.profile.tags, vec!["rust", "go"]);
+        assert_eq!(bob.extra, Some(json!({ "level": 3 })));
+    });
+}
+
+#[test]
+fn test_column_type() {
+    assert_eq!(User::INFO.columns[2].ty, ColumnType::Json);
+    assert_eq!(User::INFO.columns[3].ty, ColumnType::Json);
+    assert!(!User::INFO.columns[3].is_not_null);
+
+    // Serde json without json_column is still stored as string
+    assert_eq!(LegacyUser::INFO.columns[1].ty, ColumnType::Str(65535));
+}
+
+#[tokio::test]
+async fn test_sqlite_version() {
+    run_async_test!((repo: Repository<User>) => {
+        // `->>` of json_extract_text! needs sqlite 3.38
+        let version = repo
+            .conn
+            .query_one_map("SELECT sqlite_version() AS v", vec![], |row| async move {
+                row.get::<String>("v")
+            })
+            .await
+            .unwrap();
+        let version = version
+            .split('.')
+            .map(|v| v.parse::<u32>().unwrap())
+            .collect::<Vec<_>>();
+        assert!(version >= vec![3, 38], "sqlite version: {:?}", version);
+
+        let text = repo
+            .conn
+            .query_one_map(r#"SELECT '{"a": "x"}' ->> '$.a' AS v"#, vec![], |row| async move {
+                row.get::<String>("v")
+            })
+            .await
+            .unwrap();
+        assert_eq!(text, "x");
+    });
+}
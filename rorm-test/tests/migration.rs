@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use rorm::{
+    error::Error, Connection, Migration, MigrationContext, MigrationState, Migrator, SqlMigration,
+    ToValue,
+};
+
+struct IndexUserName;
+
+#[rorm::async_trait]
+impl Migration for IndexUserName {
+    fn version(&self) -> u64 {
+        2
+    }
+
+    fn name(&self) -> &str {
+        "index_user_name"
+    }
+
+    fn checksum(&self) -> String {
+        "1".into()
+    }
+
+    async fn up(&self, ctx: &mut MigrationContext<'_>) -> rorm::error::Result<()> {
+        // Reads are not in the transaction
+        let count = ctx
+            .conn()
+            .query_one_map("SELECT COUNT(*) AS n FROM user", vec![], |row| async move {
+                row.get::<u64>("n")
+            })
+            .await?;
+        ctx.execute("CREATE INDEX user_name ON user (name)", vec![])
+            .execute("UPDATE user SET name = name || ?", vec![count.to_value()]);
+        Ok(())
+    }
+
+    async fn down(&self, ctx: &mut MigrationContext<'_>) -> rorm::error::Result<()> {
+        ctx.execute("DROP INDEX user_name", vec![]);
+        Ok(())
+    }
+}
+
+fn create_user() -> SqlMigration {
+    SqlMigration::new(
+        1,
+        "create_user",
+        "-- Semicolons in strings and comments are kept; \n\
+         CREATE TABLE user (id INTEGER PRIMARY KEY, name TEXT NOT NULL);\n\
+         INSERT INTO user (name) VALUES ('a;b');",
+    )
+    .down("DROP TABLE user;")
+}
+
+async fn setup() -> Connection {
+    env_logger::try_init().ok();
+
+    Connection::connect("sqlite://memory").await.unwrap()
+}
+
+#[tokio::test]
+async fn test_migrate() {
+    let conn = setup().await;
+    let migrator = Migrator::new()
+        .migration(IndexUserName)
+        .migration(create_user());
+
+    let states = |statuses: Vec<rorm::MigrationStatus>| {
+        statuses
+            .into_iter()
+            .map(|status| (status.version, status.state))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        states(migrator.status(&conn).await.unwrap()),
+        vec![(1, MigrationState::Pending), (2, MigrationState::Pending)]
+    );
+
+    assert_eq!(migrator.migrate_up(&conn).await.unwrap(), vec![1, 2]);
+    assert_eq!(migrator.migrate_up(&conn).await.unwrap(), Vec::<u64>::new());
+    assert_eq!(
+        states(migrator.status(&conn).await.unwrap()),
+        vec![(1, MigrationState::Applied), (2, MigrationState::Applied)]
+    );
+    let name = conn
+        .query_one_map("SELECT name FROM user", vec![], |row| async move {
+            row.get::<String>("name")
+        })
+        .await
+        .unwrap();
+    assert_eq!(name, "a;b1");
+    assert!(conn
+        .describe_table("user")
+        .await
+        .unwrap()
+        .unwrap()
+        .index("user_name")
+        .is_some());
+
+    // Down to target version
+    assert_eq!(migrator.migrate_down(&conn, 1).await.unwrap(), vec![2]);
+    assert_eq!(
+        states(migrator.status(&conn).await.unwrap()),
+        vec![(1, MigrationState::Applied), (2, MigrationState::Pending)]
+    );
+    assert_eq!(migrator.migrate_down(&conn, 0).await.unwrap(), vec![1]);
+    assert_eq!(conn.describe_table("user").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_checksum() {
+    let conn = setup().await;
+    Migrator::new()
+        .migration(create_user())
+        .migrate_up(&conn)
+        .await
+        .unwrap();
+
+    // Applied migration is modified
+    let migrator = Migrator::new().migration(SqlMigration::new(
+        1,
+        "create_user",
+        "CREATE TABLE user (id INTEGER PRIMARY KEY)",
+    ));
+    assert!(matches!(
+        migrator.migrate_up(&conn).await,
+        Err(Error::Migration(_))
+    ));
+    assert_eq!(
+        migrator.status(&conn).await.unwrap()[0].state,
+        MigrationState::ChecksumMismatch
+    );
+
+    // Applied migration is removed
+    let migrator = Migrator::new().migration(IndexUserName);
+    assert!(matches!(
+        migrator.migrate_up(&conn).await,
+        Err(Error::Migration(_))
+    ));
+    assert_eq!(
+        migrator.status(&conn).await.unwrap()[0].state,
+        MigrationState::Missing
+    );
+
+    // Lock is released after failure
+    let migrator = Migrator::new()
+        .migration(create_user())
+        .migration(IndexUserName);
+    assert_eq!(migrator.migrate_up(&conn).await.unwrap(), vec![2]);
+}
+
+#[tokio::test]
+async fn test_atomic() {
+    let conn = setup().await;
+
+    // Statements and record are rolled back together
+    let migrator = Migrator::new().migration(SqlMigration::new(
+        1,
+        "create_tag",
+        "CREATE TABLE tag (id INTEGER); INSERT INTO missing (id) VALUES (1);",
+    ));
+    assert!(matches!(
+        migrator.migrate_up(&conn).await,
+        Err(Error::Migration(_))
+    ));
+    assert!(conn.describe_table("tag").await.unwrap().is_none());
+    assert_eq!(
+        migrator.status(&conn).await.unwrap()[0].state,
+        MigrationState::Pending
+    );
+}
+
+#[tokio::test]
+async fn test_invalid() {
+    let conn = setup().await;
+
+    let migrator = Migrator::new()
+        .migration(create_user())
+        .migration(create_user());
+    assert!(matches!(
+        migrator.migrate_up(&conn).await,
+        Err(Error::Migration(_))
+    ));
+
+    let migrator = Migrator::new().migration(SqlMigration::new(
+        1,
+        "create_tag",
+        "CREATE TABLE tag (id INTEGER)",
+    ));
+    migrator.migrate_up(&conn).await.unwrap();
+    assert!(matches!(
+        migrator.migrate_down(&conn, 0).await,
+        Err(Error::Migration(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_lock() {
+    let conn = setup().await;
+    let migrator = Migrator::new()
+        .migration(create_user())
+        .lock_timeout(Duration::from_millis(300));
+
+    // Held by another process
+    migrator.force_unlock(&conn).await.unwrap();
+    conn.execute_one(
+        "INSERT INTO rorm_migrations_lock (id, locked_at) VALUES (1, ?)",
+        vec![0u64.to_value()],
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        migrator.migrate_up(&conn).await,
+        Err(Error::Timeout(_))
+    ));
+    assert!(conn.describe_table("user").await.unwrap().is_none());
+
+    migrator.force_unlock(&conn).await.unwrap();
+    assert_eq!(migrator.migrate_up(&conn).await.unwrap(), vec![1]);
+}
+
+#[tokio::test]
+async fn test_from_dir() {
+    let conn = setup().await;
+
+    let dir = std::env::temp_dir().join(format!("rorm_migrations_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, sql) in [
+        (
+            "1_create_user.up.sql",
+            "CREATE TABLE user (id INTEGER PRIMARY KEY);",
+        ),
+        ("1_create_user.down.sql", "DROP TABLE user;"),
+        (
+            "2_create_post.up.sql",
+            "CREATE TABLE post (id INTEGER PRIMARY KEY);",
+        ),
+        ("README.md", "Not a migration"),
+    ] {
+        std::fs::write(dir.join(file), sql).unwrap();
+    }
+
+    let migrations = SqlMigration::from_dir(&dir).unwrap();
+    assert_eq!(
+        migrations
+            .iter()
+            .map(|migration| (migration.version(), migration.name()))
+            .collect::<Vec<_>>(),
+        vec![(1, "create_user"), (2, "create_post")]
+    );
+
+    let migrator = Migrator::from_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(migrator.migrate_up(&conn).await.unwrap(), vec![1, 2]);
+
+    // Migration 2 has no down sql
+    assert_eq!(migrator.migrate_down(&conn, 1).await.ok(), None);
+    assert_eq!(
+        migrator.status(&conn).await.unwrap()[1].state,
+        MigrationState::Applied
+    );
+}
@@ -0,0 +1,111 @@
+use rorm::{
+    query::{is_not_null, is_null, null_safe_eq},
+    Entity, Repository, Set, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    pub nickname: Option<String>,
+}
+
+fn user_model(name: &str, nickname: Option<&str>) -> UserModel {
+    UserModel {
+        name: name.into(),
+        nickname: nickname.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_null() {
+    run_async_test!((repo: Repository<User>) => {
+        repo.insert()
+            .models([
+                user_model("alice", Some("a")),
+                user_model("bob", None),
+                user_model("carol", None),
+            ])
+            .all()
+            .await
+            .unwrap();
+
+        let names = |users: Vec<User>| users.into_iter().map(|u| u.name).collect::<Vec<_>>();
+
+        let users = repo.find().filter(is_null!("nickname"), vec![]).all().await.unwrap();
+        assert_eq!(names(users), vec!["bob", "carol"]);
+
+        let users = repo.find().filter(is_not_null!("nickname"), vec![]).all().await.unwrap();
+        assert_eq!(names(users), vec!["alice"]);
+
+        // Null of model is matched by `IS NULL`
+        let users = repo
+            .find()
+            .filter_model(UserModel {
+                name: "bob".into(),
+                nickname: Set(None),
+                ..Default::default()
+            })
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        repo.update()
+            .set_model(UserModel {
+                nickname: Some("c").into(),
+                ..Default::default()
+            })
+            .filter_model(UserModel {
+                nickname: Set(None),
+                ..Default::default()
+            })
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find()
+                .filter(is_null!("nickname"), vec![])
+                .count()
+                .await
+                .unwrap(),
+            0
+        );
+
+        repo.delete()
+            .filter_model(UserModel {
+                nickname: Some("c").into(),
+                ..Default::default()
+            })
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(repo.find().all().await.unwrap()), vec!["alice"]);
+
+        // Nullable param is compared by `null_safe_eq!`
+        repo.insert().model(user_model("dave", None)).one().await.unwrap();
+        for (nickname, expected) in [(None, "dave"), (Some("a"), "alice")] {
+            let users = repo
+                .find()
+                .filter(
+                    null_safe_eq!("nickname", "?"),
+                    vec![nickname.map(String::from).to_value()],
+                )
+                .all()
+                .await
+                .unwrap();
+            assert_eq!(names(users), vec![expected]);
+        }
+
+        repo.delete()
+            .filter(null_safe_eq!("nickname", "?"), vec![Option::<String>::None.to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(repo.find().all().await.unwrap()), vec!["alice"]);
+    });
+}
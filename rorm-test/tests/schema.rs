@@ -0,0 +1,132 @@
+use rorm::{ColumnDesc, ColumnType, Entity, IndexDesc, Repository, SchemaChange, TableDesc};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+#[rorm(index = [email, age])]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    #[rorm(length = 20, default = "NONAME", unique)]
+    pub name: String,
+    pub email: Option<String>,
+    #[rorm(default = 18)]
+    pub age: u8,
+    pub score: f64,
+    pub avatar: Option<Vec<u8>>,
+}
+
+fn column(name: &str, ty: ColumnType, is_not_null: bool) -> ColumnDesc {
+    ColumnDesc {
+        name: name.into(),
+        ty,
+        is_primary_key: false,
+        is_not_null,
+        is_auto_increment: false,
+        default: None,
+        is_unique: false,
+    }
+}
+
+#[tokio::test]
+async fn test_list_tables() {
+    run_async_test!((repo: Repository<User>) => {
+        assert_eq!(repo.conn.list_tables().await.unwrap(), vec!["user".to_string()]);
+    });
+}
+
+#[tokio::test]
+async fn test_describe_table() {
+    run_async_test!((repo: Repository<User>) => {
+        assert_eq!(repo.conn.describe_table("not_exists").await.unwrap(), None);
+
+        let desc = repo.conn.describe_table("user").await.unwrap().unwrap();
+
+        assert_eq!(
+            desc,
+            TableDesc {
+                name: "user".into(),
+                columns: vec![
+                    ColumnDesc {
+                        is_primary_key: true,
+                        is_auto_increment: true,
+                        ..column("id", ColumnType::I64, true)
+                    },
+                    ColumnDesc {
+                        default: Some("'NONAME'".into()),
+                        is_unique: true,
+                        ..column("name", ColumnType::Str(65535), true)
+                    },
+                    column("email", ColumnType::Str(65535), false),
+                    ColumnDesc {
+                        default: Some("18".into()),
+                        ..column("age", ColumnType::I64, true)
+                    },
+                    column("score", ColumnType::F64, true),
+                    column("avatar", ColumnType::Bytes(65535), false),
+                ],
+                indexes: vec![IndexDesc {
+                    name: "user_index_email_age".into(),
+                    keys: vec!["email".into(), "age".into()],
+                    is_unique: false,
+                }],
+            }
+        );
+
+        // Same as table info except the types reported by sqlite
+        let mut expected = TableDesc::from(&User::INFO);
+        for col in expected.columns.iter_mut() {
+            col.ty = desc.column(&col.name).unwrap().ty.clone();
+        }
+        assert_eq!(desc, expected);
+
+        // Unique index created by user is not treated as unique column
+        repo.conn
+            .execute_one("CREATE UNIQUE INDEX user_score ON user (score)", vec![])
+            .await
+            .unwrap();
+        let desc = repo.conn.describe_table("user").await.unwrap().unwrap();
+        assert!(!desc.column("score").unwrap().is_unique);
+        assert_eq!(
+            desc.index("user_score"),
+            Some(&IndexDesc {
+                name: "user_score".into(),
+                keys: vec!["score".into()],
+                is_unique: true,
+            })
+        );
+    });
+}
+
+#[tokio::test]
+async fn test_diff_table() {
+    run_async_test!((repo: Repository<User>) => {
+        let mut expected = repo.conn.describe_table("user").await.unwrap().unwrap();
+        assert_eq!(repo.conn.diff_table(&expected).await.unwrap(), vec![]);
+
+        // Missing table is diffed with an empty table
+        expected.name = "user_copy".into();
+        let changes = repo.conn.diff_table(&expected).await.unwrap();
+        assert_eq!(changes.len(), expected.columns.len() + expected.indexes.len());
+        assert!(matches!(&changes[0], SchemaChange::AddColumn(col) if col.name == "id"));
+    });
+}
+
+#[tokio::test]
+async fn test_gen_ddl() {
+    run_async_test!((repo: Repository<User>) => {
+        let mut desc = repo.conn.describe_table("user").await.unwrap().unwrap();
+        desc.name = "user_copy".into();
+        desc.indexes[0].name = "user_copy_index_email_age".into();
+
+        let sqls = repo.conn.gen_ddl(&desc);
+        assert_eq!(sqls.len(), 2);
+        repo.conn
+            .execute_many(sqls.into_iter().map(|sql| (sql, vec![vec![]])).collect())
+            .await
+            .unwrap();
+
+        let copy = repo.conn.describe_table("user_copy").await.unwrap().unwrap();
+        assert_eq!(copy, desc);
+    });
+}
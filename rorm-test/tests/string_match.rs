@@ -0,0 +1,75 @@
+use rorm::{
+    query::{
+        glob, ilike, like_contains, like_ends_with, like_escape, like_starts_with, not_like,
+        regexp, sql_str, Where,
+    },
+    Entity, Repository, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "product")]
+struct Product {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+}
+
+#[test]
+fn test_pattern() {
+    assert_eq!(like_contains("50%_off!"), "%50!%!_off!!%");
+    assert_eq!(like_starts_with("a_b"), "a!_b%");
+    assert_eq!(like_ends_with("abc"), "%abc");
+}
+
+#[tokio::test]
+async fn test_find() {
+    run_async_test!((repo: Repository<Product>) => {
+        for name in ["Apple", "apple pie", "50% off", "500 off", "a_b.rs"] {
+            repo.insert()
+                .model(ProductModel { name: name.into(), ..Default::default() })
+                .one()
+                .await
+                .unwrap();
+        }
+
+        let find = |cond: Where, param: &str| {
+            let repo = &repo;
+            let params = vec![param.to_string().to_value()];
+            async move {
+                repo.find()
+                    .filter(cond, params)
+                    .order_by("id", true)
+                    .all()
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|p| p.name)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            find(not_like!("name", "?"), "%off").await,
+            vec!["Apple", "apple pie", "a_b.rs"]
+        );
+        assert_eq!(find(ilike!("name", "?"), "APPLE%").await, vec!["Apple", "apple pie"]);
+
+        // `%` and `_` of input are matched literally
+        assert_eq!(find(like_escape!("name", "?"), &like_contains("50%")).await, vec!["50% off"]);
+        assert_eq!(find(like_escape!("name", "?"), &like_starts_with("a_")).await, vec!["a_b.rs"]);
+
+        // Glob is case sensitive
+        assert_eq!(find(glob!("name", "?"), "a*").await, vec!["apple pie", "a_b.rs"]);
+        assert_eq!(
+            repo.find().filter(glob!("name", sql_str("*.rs")), vec![]).count().await.unwrap(),
+            1
+        );
+
+        assert_eq!(find(regexp!("name", "?"), r"^\d+% ").await, vec!["50% off"]);
+        assert_eq!(find(regexp!("name", "?"), "(?i)^apple$").await, vec!["Apple"]);
+
+        // Invalid regular expression
+        assert!(repo.find().filter(regexp!("name", "?"), vec!["(".to_string().to_value()]).all().await.is_err());
+    });
+}
@@ -0,0 +1,145 @@
+use rorm::{
+    query::{and, eq, exists, gt, in_select, lt, ne, not_exists, subquery, QueryBuilder},
+    Entity, Repository, ToValue, Value,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+    pub age: u32,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "post")]
+struct Post {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub user_id: u32,
+    pub title: String,
+}
+
+fn user_model(name: &str, age: u32) -> UserModel {
+    UserModel {
+        name: name.into(),
+        age: age.into(),
+        ..Default::default()
+    }
+}
+
+fn post_model(user_id: u32, title: &str) -> PostModel {
+    PostModel {
+        user_id: user_id.into(),
+        title: title.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_build() {
+    let sub = Post::find()
+        .filter_model(PostModel {
+            title: "rust".into(),
+            ..Default::default()
+        })
+        .subquery("user_id");
+    assert_eq!(sub.bound_params::<Vec<Value>>().unwrap().len(), 1);
+
+    let sql = QueryBuilder::select("user")
+        .column("*")
+        .where_cond(and!(in_select!("id", sub), gt!("age", "?")))
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT * FROM user WHERE ((id IN (SELECT user_id FROM post WHERE (post.title = ?))) AND (age > ?))"
+    );
+
+    // Invalid subquery fails the outer query
+    assert!(QueryBuilder::select("user")
+        .column("*")
+        .where_cond(exists!(QueryBuilder::select("post")))
+        .build()
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_find() {
+    run_async_test!((user_repo: Repository<User>, post_repo: Repository<Post>) => {
+        let ids = user_repo
+            .insert()
+            .models([user_model("alice", 20), user_model("bob", 30), user_model("carol", 40)])
+            .all()
+            .await
+            .unwrap();
+        let (alice, bob) = (ids[0], ids[1]);
+        post_repo
+            .insert()
+            .models([post_model(alice, "rust"), post_model(bob, "rust"), post_model(bob, "go")])
+            .all()
+            .await
+            .unwrap();
+
+        let names = |users: Vec<User>| users.into_iter().map(|u| u.name).collect::<Vec<_>>();
+
+        // Params of subquery are merged at its position
+        let rust_posts = || {
+            Post::find()
+                .filter(eq!("title", "?"), vec!["rust".to_string().to_value()])
+                .subquery("user_id")
+        };
+        let users = user_repo
+            .find()
+            .filter(and!(gt!("age", "?"), in_select!("id", rust_posts())), vec![25.to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        let users = user_repo
+            .find()
+            .filter(and!(in_select!("id", rust_posts()), lt!("age", "?")), vec![25.to_value()])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["alice"]);
+
+        // Correlated exists
+        let sub = Post::find()
+            .filter(and!(eq!("post.user_id", "user.id"), ne!("post.title", "'rust'")), vec![])
+            .subquery("1");
+        let users = user_repo.find().filter(exists!(sub), vec![]).all().await.unwrap();
+        assert_eq!(names(users), vec!["bob"]);
+
+        let sub = Post::find()
+            .filter(eq!("post.user_id", "user.id"), vec![])
+            .subquery("1");
+        let users = user_repo.find().filter(not_exists!(sub), vec![]).all().await.unwrap();
+        assert_eq!(names(users), vec!["carol"]);
+
+        // Scalar subquery
+        let users = user_repo
+            .find()
+            .filter(
+                gt!("age", subquery!(QueryBuilder::select("user").column("AVG(age)").clone())),
+                vec![],
+            )
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(names(users), vec!["carol"]);
+
+        // Subquery in delete
+        user_repo
+            .delete()
+            .filter(and!(in_select!("id", rust_posts()), gt!("age", "?")), vec![25.to_value()])
+            .all()
+            .await
+            .unwrap();
+        let users = user_repo.find().all().await.unwrap();
+        assert_eq!(names(users), vec!["alice", "carol"]);
+    });
+}
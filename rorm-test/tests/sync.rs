@@ -0,0 +1,227 @@
+use rorm::{ColumnType, Connection, Entity, SchemaChange, SyncMode};
+
+mod v1 {
+    use rorm::Entity;
+
+    #[derive(Debug, Entity)]
+    #[rorm(table_name = "user")]
+    pub struct User {
+        #[rorm(primary_key, auto_increment)]
+        pub id: u32,
+        pub name: String,
+        pub age: String,
+        pub nickname: Option<String>,
+    }
+}
+
+mod v2 {
+    use rorm::Entity;
+
+    #[derive(Debug, PartialEq, Eq, Entity)]
+    #[rorm(table_name = "user")]
+    #[rorm(index = [name])]
+    pub struct User {
+        #[rorm(primary_key, auto_increment)]
+        pub id: u32,
+        pub name: String,
+        pub age: u32,
+        pub email: Option<String>,
+        #[rorm(default = 1)]
+        pub level: u8,
+    }
+}
+
+mod pk_v1 {
+    use rorm::Entity;
+
+    #[derive(Debug, Entity)]
+    #[rorm(table_name = "account")]
+    pub struct Account {
+        #[rorm(primary_key, auto_increment)]
+        pub id: u32,
+        pub code: u32,
+    }
+}
+
+mod pk_v2 {
+    use rorm::Entity;
+
+    #[derive(Debug, PartialEq, Eq, Entity)]
+    #[rorm(table_name = "account")]
+    pub struct Account {
+        pub id: u32,
+        #[rorm(primary_key)]
+        pub code: u32,
+    }
+}
+
+async fn setup() -> Connection {
+    env_logger::try_init().ok();
+
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+    let repo = conn.repository::<v1::User>();
+    repo.init().await.unwrap();
+    repo.insert()
+        .model(v1::UserModel {
+            name: "bob".into(),
+            age: "20".into(),
+            nickname: Some("b").into(),
+            ..Default::default()
+        })
+        .one()
+        .await
+        .unwrap();
+
+    conn
+}
+
+#[tokio::test]
+async fn test_sync_unchanged() {
+    let conn = setup().await;
+
+    let diff = conn
+        .repository::<v1::User>()
+        .init_with(SyncMode::Destructive)
+        .await
+        .unwrap();
+
+    assert!(diff.is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_safe() {
+    let conn = setup().await;
+    let repo = conn.repository::<v2::User>();
+
+    let diff = repo.init_with(SyncMode::Safe).await.unwrap();
+    let applied_names = diff
+        .applied
+        .iter()
+        .map(|change| match change {
+            SchemaChange::AddColumn(col) => col.name.clone(),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(applied_names, vec!["email", "level"]);
+    assert!(matches!(
+        &diff.pending[..],
+        [SchemaChange::AlterColumn { from, .. }, SchemaChange::DropColumn(dropped)]
+            if from.name == "age" && dropped.name == "nickname"
+    ));
+
+    // Index is created by init, new columns of old rows are filled with default value
+    let desc = conn.describe_table("user").await.unwrap().unwrap();
+    assert_eq!(desc.column("age").unwrap().ty, ColumnType::Str(65535));
+    assert!(desc.column("nickname").is_some());
+    assert!(desc.index("user_index_name").is_some());
+    let (email, level) = conn
+        .query_one_map("SELECT email, level FROM user", vec![], |row| async move {
+            Ok((row.get::<Option<String>>("email")?, row.get::<u8>("level")?))
+        })
+        .await
+        .unwrap();
+    assert_eq!(email, None);
+    assert_eq!(level, 1);
+
+    // Pending changes are reported again
+    let diff = repo.init_with(SyncMode::Safe).await.unwrap();
+    assert!(diff.applied.is_empty());
+    assert_eq!(diff.pending.len(), 2);
+}
+
+#[tokio::test]
+async fn test_sync_destructive() {
+    let conn = setup().await;
+    let repo = conn.repository::<v2::User>();
+
+    let diff = repo.init_with(SyncMode::Destructive).await.unwrap();
+    assert_eq!(diff.applied.len(), 4);
+    assert!(diff.pending.is_empty());
+
+    // Table is same as table info
+    let desc = conn.describe_table("user").await.unwrap().unwrap();
+    assert_eq!(
+        desc.columns
+            .iter()
+            .map(|col| col.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["id", "name", "age", "email", "level"]
+    );
+    assert_eq!(desc.column("age").unwrap().ty, ColumnType::I64);
+    assert_eq!(desc.indexes.len(), 1);
+    assert!(repo
+        .init_with(SyncMode::Destructive)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Rows are kept, and autoincrement continues
+    let id = repo
+        .insert()
+        .model(v2::UserModel {
+            name: "alice".into(),
+            age: 18.into(),
+            ..Default::default()
+        })
+        .one()
+        .await
+        .unwrap();
+    assert_eq!(id, 2);
+    assert_eq!(
+        repo.find().filter_model(1).one().await.unwrap(),
+        v2::User {
+            id: 1,
+            name: "bob".into(),
+            age: 20,
+            email: None,
+            level: 1,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_sync_info() {
+    let conn = setup().await;
+
+    // Entity can be synced without repository
+    let diff = v2::User::init_with(&conn, SyncMode::Safe).await.unwrap();
+    assert_eq!(diff.applied.len(), 2);
+}
+
+#[tokio::test]
+async fn test_sync_primary_key() {
+    env_logger::try_init().ok();
+
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+    let repo = conn.repository::<pk_v1::Account>();
+    repo.init().await.unwrap();
+    repo.insert()
+        .model(pk_v1::AccountModel {
+            code: 7.into(),
+            ..Default::default()
+        })
+        .one()
+        .await
+        .unwrap();
+
+    // Primary key is moved to another column
+    let repo = conn.repository::<pk_v2::Account>();
+    let diff = repo.init_with(SyncMode::Destructive).await.unwrap();
+    assert_eq!(diff.applied.len(), 2);
+
+    let desc = conn.describe_table("account").await.unwrap().unwrap();
+    assert!(!desc.column("id").unwrap().is_primary_key);
+    assert!(!desc.column("id").unwrap().is_auto_increment);
+    assert!(desc.column("code").unwrap().is_primary_key);
+
+    // Old primary key is not reported again
+    assert!(repo
+        .init_with(SyncMode::Destructive)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        repo.find().filter_model(7).one().await.unwrap(),
+        pk_v2::Account { id: 1, code: 7 }
+    );
+}
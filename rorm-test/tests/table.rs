@@ -0,0 +1,108 @@
+use rorm::{Connection, Entity, Repository};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "address")]
+struct Address {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub user_id: u32,
+}
+
+fn user_model(name: &str) -> UserModel {
+    UserModel {
+        name: name.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_truncate() {
+    run_async_test!((repo: Repository<User>) => {
+        repo.insert()
+            .models([user_model("bob"), user_model("alice")])
+            .all()
+            .await
+            .unwrap();
+
+        repo.truncate().await.unwrap();
+        assert!(repo.find().all().await.unwrap().is_empty());
+
+        // Auto increment counter is reset
+        assert_eq!(repo.insert().model(user_model("bob")).one().await.unwrap(), 1);
+    });
+}
+
+#[tokio::test]
+async fn test_truncate_without_sequence() {
+    env_logger::try_init().ok();
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+
+    // Sqlite creates `sqlite_sequence` with the first AUTOINCREMENT table only
+    conn.execute_one(
+        "CREATE TABLE tag (id INTEGER PRIMARY KEY, name TEXT)",
+        vec![],
+    )
+    .await
+    .unwrap();
+    conn.truncate_table("tag").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_drop_table() {
+    run_async_test!((repo: Repository<User>) => {
+        repo.drop_table().await.unwrap();
+        assert_eq!(repo.conn.describe_table("user").await.unwrap(), None);
+
+        assert!(repo.drop_table().await.is_err());
+        repo.drop_if_exists().await.unwrap();
+    });
+}
+
+#[tokio::test]
+async fn test_rename_table() {
+    run_async_test!((repo: Repository<User>) => {
+        repo.insert().model(user_model("bob")).one().await.unwrap();
+
+        repo.rename_table("user_backup").await.unwrap();
+        assert_eq!(repo.conn.list_tables().await.unwrap(), vec!["user_backup".to_string()]);
+
+        // Rows are moved with table
+        repo.init().await.unwrap();
+        assert!(repo.find().all().await.unwrap().is_empty());
+        let name = repo
+            .conn
+            .query_one_map("SELECT name FROM user_backup", vec![], |row| async move {
+                row.get::<String>("name")
+            })
+            .await
+            .unwrap();
+        assert_eq!(name, "bob");
+    });
+}
+
+#[tokio::test]
+async fn test_init_drop_all() {
+    env_logger::try_init().ok();
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+
+    conn.init_all::<(User, Address)>().await.unwrap();
+    assert_eq!(
+        conn.list_tables().await.unwrap(),
+        vec!["address".to_string(), "user".to_string()]
+    );
+
+    conn.drop_all::<(User, Address)>().await.unwrap();
+    assert!(conn.list_tables().await.unwrap().is_empty());
+
+    // Missing tables are ignored
+    conn.drop_all::<(User,)>().await.unwrap();
+}
@@ -0,0 +1,133 @@
+use rorm::{
+    query::{eq, gt, lt},
+    ColumnType, Entity, Repository, ToValue,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "file")]
+struct File {
+    #[rorm(primary_key)]
+    pub id: u64, // Snowflake id
+    pub hash: u64,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "log")]
+struct Log {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u64,
+    pub seq: u64,
+}
+
+#[tokio::test]
+async fn test_u64() {
+    run_async_test!((repo: Repository<File>) => {
+        let values = [0, i64::MAX as u64, i64::MAX as u64 + 1, u64::MAX];
+        for (i, v) in values.iter().enumerate() {
+            repo.insert()
+                .model(FileModel {
+                    id: (u64::MAX - i as u64).into(),
+                    hash: (*v).into(),
+                    size: Some(*v).into(),
+                })
+                .one()
+                .await
+                .unwrap();
+        }
+
+        let hashes = |files: Vec<File>| files.into_iter().map(|f| f.hash).collect::<Vec<_>>();
+
+        // Order is kept across i64::MAX
+        let files = repo.find().order_by("hash", true).all().await.unwrap();
+        assert_eq!(hashes(files), values);
+        let files = repo.find().order_by("hash", false).all().await.unwrap();
+        assert_eq!(hashes(files), values.iter().rev().copied().collect::<Vec<_>>());
+
+        let files = repo
+            .find()
+            .filter(gt!("hash", "?"), vec![5u64.to_value()])
+            .order_by("hash", true)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(hashes(files), values[1..]);
+        let files = repo
+            .find()
+            .filter(lt!("hash", "?"), vec![u64::MAX.to_value()])
+            .order_by("hash", true)
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(hashes(files), values[..3]);
+
+        assert_eq!(repo.find().max::<Option<u64>>("hash").await.unwrap(), Some(u64::MAX));
+        assert_eq!(repo.find().min::<Option<u64>>("hash").await.unwrap(), Some(0));
+        let max = repo
+            .find()
+            .filter(lt!("hash", "?"), vec![u64::MAX.to_value()])
+            .max::<Option<u64>>("hash")
+            .await
+            .unwrap();
+        assert_eq!(max, Some(i64::MAX as u64 + 1));
+
+        // Sum fails instead of treating values above i64::MAX as 0
+        assert!(repo.find().sum::<Option<u64>>("hash").await.is_err());
+        let sum = repo
+            .find()
+            .filter(lt!("hash", "?"), vec![(i64::MAX as u64 + 1).to_value()])
+            .sum::<Option<u64>>("hash")
+            .await
+            .unwrap();
+        assert_eq!(sum, Some(i64::MAX as u64));
+
+        for v in values {
+            let file = repo
+                .find()
+                .filter(eq!("hash", "?"), vec![v.to_value()])
+                .one()
+                .await
+                .unwrap();
+            assert_eq!(file.hash, v);
+            assert_eq!(file.size, Some(v));
+        }
+
+        let file = repo.find().filter_model(u64::MAX - 3).one().await.unwrap();
+        assert_eq!(file.hash, u64::MAX);
+
+        // Increment fails instead of treating values above i64::MAX as 0
+        let id = u64::MAX - 2;
+        assert!(repo.increment("hash", 1).filter_model(id).all().await.is_err());
+        let file = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(file.hash, i64::MAX as u64 + 1);
+        repo.increment("hash", 5).filter_model(u64::MAX).all().await.unwrap();
+        let file = repo.find().filter_model(u64::MAX).one().await.unwrap();
+        assert_eq!(file.hash, 5);
+
+        let desc = repo.conn.describe_table("file").await.unwrap().unwrap();
+        assert_eq!(desc.columns[1].ty, ColumnType::U64);
+        assert!(repo.conn.diff_table(&desc).await.unwrap().is_empty());
+    });
+}
+
+#[tokio::test]
+async fn test_auto_increment() {
+    run_async_test!((repo: Repository<Log>) => {
+        let id = repo
+            .insert()
+            .model(LogModel {
+                seq: u64::MAX.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+        assert_eq!(id, 1);
+
+        let log = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(log.seq, u64::MAX);
+
+        assert!(repo.init_with(rorm::SyncMode::Safe).await.unwrap().is_empty());
+    });
+}
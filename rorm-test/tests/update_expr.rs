@@ -0,0 +1,95 @@
+use rorm::{query::QueryBuilder, Entity, Repository, ToValue};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "post")]
+struct Post {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub title: String,
+    pub slug: Option<String>,
+    pub views: i64,
+    pub likes: Option<u32>,
+}
+
+#[test]
+fn test_build() {
+    let sql = QueryBuilder::update("post")
+        .set("title", "CONCAT(title, ?)".into())
+        .increment("views", 1.into())
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        "UPDATE post SET title = CONCAT(title, ?), views = views + 1 "
+    );
+}
+
+#[tokio::test]
+async fn test_update() {
+    run_async_test!((repo: Repository<Post>) => {
+        let id = repo
+            .insert()
+            .model(PostModel {
+                title: "rust".into(),
+                views: 10.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+        let other = repo
+            .insert()
+            .model(PostModel {
+                title: "go".into(),
+                views: 0.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+
+        // Counter
+        repo.increment("views", 5).filter_model(id).all().await.unwrap();
+        repo.increment("views", -2).filter_model(id).all().await.unwrap();
+        assert_eq!(repo.find().filter_model(id).one().await.unwrap().views, 13);
+        assert_eq!(repo.find().filter_model(other).one().await.unwrap().views, 0);
+
+        // Params are bound in order of set, set_model and increment
+        repo.update()
+            .set("title", "CONCAT(title, ?)".into(), vec!["-lang".to_string().to_value()])
+            .set_model(PostModel {
+                slug: Some("rust".to_string()).into(),
+                ..Default::default()
+            })
+            .increment("views", 1)
+            .set("likes", "COALESCE(likes, 0) + ?".into(), vec![3.to_value()])
+            .filter_model(id)
+            .all()
+            .await
+            .unwrap();
+        let post = repo.find().filter_model(id).one().await.unwrap();
+        assert_eq!(post.title, "rust-lang");
+        assert_eq!(post.slug.as_deref(), Some("rust"));
+        assert_eq!(post.views, 14);
+        assert_eq!(post.likes, Some(3));
+
+        // Set from another column
+        repo.update()
+            .set("slug", "title".into(), vec![])
+            .all()
+            .await
+            .unwrap();
+        let post = repo.find().filter_model(other).one().await.unwrap();
+        assert_eq!(post.slug.as_deref(), Some("go"));
+
+        // Concat with null is null
+        repo.update()
+            .set("slug", "CONCAT(title, likes)".into(), vec![])
+            .all()
+            .await
+            .unwrap();
+        let post = repo.find().filter_model(other).one().await.unwrap();
+        assert_eq!(post.slug, None);
+    });
+}
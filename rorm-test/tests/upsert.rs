@@ -0,0 +1,117 @@
+use rorm::{query::Dialect, Entity, Repository, ToValue};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    #[rorm(length = 20, unique)]
+    pub name: String,
+    pub email: Option<String>,
+    pub logins: u32,
+}
+
+fn user_model(name: &str, email: &str) -> UserModel {
+    UserModel {
+        name: name.into(),
+        email: Some(email.to_string()).into(),
+        logins: 1.into(),
+        ..Default::default()
+    }
+}
+
+fn rows(users: Vec<User>) -> Vec<(String, String, u32)> {
+    users
+        .into_iter()
+        .map(|u| (u.name, u.email.unwrap_or_default(), u.logins))
+        .collect()
+}
+
+fn row(name: &str, email: &str, logins: u32) -> (String, String, u32) {
+    (name.into(), email.into(), logins)
+}
+
+#[tokio::test]
+async fn test_upsert() {
+    run_async_test!((repo: Repository<User>) => {
+        assert_eq!(repo.conn.dialect(), Dialect::Sqlite);
+
+        // Insert if not conflict
+        repo.upsert()
+            .models([user_model("bob", "bob@a.com"), user_model("alice", "alice@a.com")])
+            .on_conflict(["name"])
+            .execute()
+            .await
+            .unwrap();
+
+        // Every inserted column except target is updated by default
+        repo.upsert()
+            .model(user_model("bob", "bob@b.com"))
+            .on_conflict(["name"])
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(
+            rows(repo.find().order_by("id", true).all().await.unwrap()),
+            vec![row("bob", "bob@b.com", 1), row("alice", "alice@a.com", 1)]
+        );
+
+        // Update chosen columns, others keep existing values
+        repo.upsert()
+            .models([user_model("alice", "alice@c.com"), user_model("carol", "carol@c.com")])
+            .on_conflict(["name"])
+            .set("logins", "logins + ?".into(), vec![2.to_value()])
+            .update(["email"])
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(
+            rows(repo.find().order_by("id", true).all().await.unwrap()),
+            vec![
+                row("bob", "bob@b.com", 1),
+                row("alice", "alice@c.com", 3),
+                row("carol", "carol@c.com", 1),
+            ]
+        );
+
+        // Keep existing rows
+        repo.upsert()
+            .model(user_model("carol", "carol@d.com"))
+            .on_conflict(["name"])
+            .do_nothing()
+            .execute()
+            .await
+            .unwrap();
+        let carol = repo.find().filter_model(user_model("carol", "carol@c.com")).all().await.unwrap();
+        assert_eq!(rows(carol), vec![row("carol", "carol@c.com", 1)]);
+
+        // Conflict on primary key by default, auto increment id may be consumed by conflicts
+        repo.upsert()
+            .model(UserModel {
+                id: 1.into(),
+                ..user_model("dave", "dave@d.com")
+            })
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(
+            rows(vec![repo.find().filter_model(1).one().await.unwrap()]),
+            vec![row("dave", "dave@d.com", 1)]
+        );
+
+        // Entity builder
+        User::upsert()
+            .model(user_model("dave", "dave@e.com"))
+            .on_conflict(["name"])
+            .update(["email"])
+            .execute(&repo.conn)
+            .await
+            .unwrap();
+        assert_eq!(
+            rows(vec![repo.find().filter_model(1).one().await.unwrap()]),
+            vec![row("dave", "dave@e.com", 1)]
+        );
+        assert_eq!(repo.find().count().await.unwrap(), 3);
+    });
+}
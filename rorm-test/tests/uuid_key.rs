@@ -0,0 +1,92 @@
+use rorm::{ColumnType, Entity, Model, Repository};
+use rorm_test::run_async_test;
+use uuid::Uuid;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "user")]
+struct User {
+    #[rorm(primary_key)]
+    pub id: Uuid,
+    pub name: String,
+    #[rorm(uuid = text)]
+    pub invited_by: Option<Uuid>,
+}
+
+#[tokio::test]
+async fn test_uuid_primary_key() {
+    run_async_test!((repo: Repository<User>) => {
+        // Key is generated by client
+        let bob_id = repo
+            .insert()
+            .model(UserModel {
+                name: "bob".into(),
+                invited_by: None.into(),
+                ..Default::default()
+            })
+            .one()
+            .await
+            .unwrap();
+        assert_ne!(bob_id, Uuid::nil());
+        // Inserted id of database is not converted to uuid
+        assert_eq!(<UserModel as Model<Uuid>>::to_primary_key(1), None);
+
+        // Key set by user is returned as is
+        let alice_id = Uuid::from_u128(0x1234);
+        let ids = repo
+            .insert()
+            .models([
+                UserModel {
+                    id: alice_id.into(),
+                    name: "alice".into(),
+                    invited_by: Some(bob_id).into(),
+                },
+                UserModel {
+                    name: "carol".into(),
+                    ..Default::default()
+                },
+            ])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], alice_id);
+        assert_ne!(ids[1], bob_id);
+
+        let alice = repo.find().filter_model(alice_id).one().await.unwrap();
+        assert_eq!(alice.name, "alice");
+        assert_eq!(alice.invited_by, Some(bob_id));
+
+        // Filter on text column
+        let invited = repo
+            .find()
+            .filter_model(UserModel {
+                invited_by: Some(bob_id).into(),
+                ..Default::default()
+            })
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(invited.len(), 1);
+
+        // Stored as 16 bytes blob and hyphenated text
+        let (id, invited_by) = repo
+            .conn
+            .query_one_map(
+                "SELECT id, invited_by FROM user WHERE name = 'alice'",
+                vec![],
+                |row| async move {
+                    Ok((row.get::<Vec<u8>>("id")?, row.get::<String>("invited_by")?))
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(id, alice_id.as_bytes().to_vec());
+        assert_eq!(invited_by, bob_id.hyphenated().to_string());
+    });
+}
+
+#[test]
+fn test_column_type() {
+    assert_eq!(User::INFO.columns[0].ty, ColumnType::Uuid);
+    assert_eq!(User::INFO.columns[2].ty, ColumnType::UuidText);
+}
@@ -0,0 +1,219 @@
+use std::collections::HashSet;
+
+use rorm::{
+    query::Dialect, Connection, Date, DateTime, Decimal, Entity, FromValue, Repository, Row,
+    ToValue, Value,
+};
+use rorm_test::run_async_test;
+
+#[derive(Debug, Entity)]
+#[rorm(table_name = "counter")]
+struct Counter {
+    #[rorm(primary_key, auto_increment)]
+    pub id: u32,
+    pub strict: u8,
+    #[rorm(lossy)]
+    pub lossy: u8,
+}
+
+#[test]
+fn test_integer() {
+    assert_eq!(u32::from_value(&Value::I64(42)).unwrap(), 42);
+    assert!(u32::from_value(&Value::I64(-1)).is_err());
+    assert!(u32::from_value(&Value::I64(1 << 32)).is_err());
+    assert!(i64::from_value(&Value::U64(u64::MAX)).is_err());
+    assert_eq!(i8::from_value(&Value::I64(-128)).unwrap(), -128);
+    assert!(u8::from_value(&Value::F64(1.0)).is_err());
+
+    // Numeric text
+    assert_eq!(u16::from_value(&Value::Str("65535".into())).unwrap(), 65535);
+    assert_eq!(i32::from_value(&Value::Bytes(b"-7".to_vec())).unwrap(), -7);
+    // Numeric text of 8 bytes, e.g. decimal or sum of mysql
+    assert_eq!(
+        u64::from_value(&Value::Bytes(b"12345678".to_vec())).unwrap(),
+        12345678
+    );
+    assert_eq!(
+        i64::from_value(&Value::Bytes(b"-1234567".to_vec())).unwrap(),
+        -1234567
+    );
+    assert!(u16::from_value(&Value::Str("65536".into())).is_err());
+    assert!(u16::from_value(&Value::Str("abc".into())).is_err());
+
+    // Opt-in lossy conversion
+    assert_eq!(u32::from_value_lossy(&Value::I64(-1)).unwrap(), u32::MAX);
+    assert_eq!(u8::from_value_lossy(&Value::F64(2.9)).unwrap(), 2);
+    assert_eq!(
+        Option::<u8>::from_value_lossy(&Value::I64(256)).unwrap(),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_float() {
+    assert_eq!(
+        f64::from_value(&Value::I64(1 << 53)).unwrap(),
+        9007199254740992.0
+    );
+    assert!(f64::from_value(&Value::I64((1 << 53) + 1)).is_err());
+    assert_eq!(f32::from_value(&Value::U32(1 << 24)).unwrap(), 16777216.0);
+    assert!(f32::from_value(&Value::U32((1 << 24) + 1)).is_err());
+    assert_eq!(f64::from_value(&Value::F32(0.1)).unwrap(), 0.1f32 as f64);
+    assert_eq!(f32::from_value(&Value::F64(0.5)).unwrap(), 0.5);
+    assert!(f32::from_value(&Value::F64(0.1)).is_err());
+    assert!(f32::from_value(&Value::F64(f64::NAN)).unwrap().is_nan());
+    assert_eq!(f64::from_value(&Value::Str("1.25".into())).unwrap(), 1.25);
+
+    assert_eq!(f32::from_value_lossy(&Value::F64(0.1)).unwrap(), 0.1);
+    assert_eq!(
+        f64::from_value_lossy(&Value::U64(u64::MAX)).unwrap(),
+        u64::MAX as f64
+    );
+}
+
+#[test]
+fn test_eq_and_hash() {
+    assert_eq!(Value::Str("a".into()).clone(), Value::Str("a".into()));
+    assert_ne!(Value::I32(1), Value::I64(1));
+    assert_eq!(Value::F64(f64::NAN), Value::F64(f64::NAN));
+    assert_ne!(Value::F64(0.0), Value::F64(-0.0));
+
+    let set = [
+        Value::Null,
+        Value::F64(f64::NAN),
+        Value::F64(f64::NAN),
+        Value::I32(1),
+        Value::I64(1),
+    ]
+    .into_iter()
+    .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(Value::Null.to_string(), "NULL");
+    assert_eq!(Value::Bool(true).to_string(), "1");
+    assert_eq!(Value::I64(-3).to_string(), "-3");
+    assert_eq!(Value::F64(1.0).to_string(), "1.0");
+    assert_eq!(Value::Str("it's".into()).to_string(), "'it''s'");
+    assert_eq!(Value::Bytes(vec![0, 255]).to_string(), "X'00FF'");
+    assert_eq!(
+        Value::Date(Date::new(2022, 1, 2).unwrap()).to_string(),
+        "'2022-01-02'"
+    );
+}
+
+#[tokio::test]
+async fn test_sql_literal() {
+    let s = Value::Str(r"a\b'c".into());
+    assert_eq!(s.to_sql_literal(Dialect::Sqlite).unwrap(), r"'a\b''c'");
+    assert_eq!(s.to_sql_literal(Dialect::Mysql).unwrap(), r"'a\\b''c'");
+
+    let big = Value::U64(u64::MAX);
+    assert_eq!(
+        big.to_sql_literal(Dialect::Sqlite).unwrap(),
+        "X'FFFFFFFFFFFFFFFF'"
+    );
+    assert_eq!(
+        big.to_sql_literal(Dialect::Mysql).unwrap(),
+        "18446744073709551615"
+    );
+    assert_eq!(Value::U64(1).to_sql_literal(Dialect::Sqlite).unwrap(), "1");
+
+    assert!(Value::F64(f64::NAN)
+        .to_sql_literal(Dialect::Sqlite)
+        .is_err());
+    assert!(Value::F32(f32::INFINITY)
+        .to_sql_literal(Dialect::Mysql)
+        .is_err());
+    assert_eq!(
+        Value::F64(1.5).to_sql_literal(Dialect::Mysql).unwrap(),
+        "1.5"
+    );
+
+    // Read back as the same value by sqlite, blob of u64 without declared type is read by
+    // `u64_value`
+    let conn = Connection::connect("sqlite://memory").await.unwrap();
+    let sql = format!(
+        "SELECT {} AS s, u64_value({}) AS big",
+        s.to_sql_literal(Dialect::Sqlite).unwrap(),
+        big.to_sql_literal(Dialect::Sqlite).unwrap()
+    );
+    let (text, n) = conn
+        .query_one_map(&sql, vec![], |row| async move {
+            Ok((row.get::<String>("s")?, row.get::<u64>("big")?))
+        })
+        .await
+        .unwrap();
+    assert_eq!(text, r"a\b'c");
+    assert_eq!(n, u64::MAX);
+}
+
+#[test]
+fn test_serde() {
+    let values = vec![
+        Value::Null,
+        Value::U64(u64::MAX),
+        Value::F32(1.5),
+        Value::Str("abc".into()),
+        Value::Bytes(vec![1, 2]),
+        Value::DateTime("2022-01-02 03:04:05.5".parse::<DateTime>().unwrap()),
+        Value::Decimal("-1.25".parse::<Decimal>().unwrap()),
+        Value::Uuid([7; 16]),
+    ];
+    let json = serde_json::to_string(&values).unwrap();
+    assert!(json.contains(r#"{"DateTime":"2022-01-02 03:04:05.5"}"#));
+    assert_eq!(serde_json::from_str::<Vec<Value>>(&json).unwrap(), values);
+
+    // Table info is only serializable
+    let json = serde_json::to_value(&Counter::INFO).unwrap();
+    assert_eq!(json["name"], "counter");
+    assert_eq!(json["columns"][0]["ty"], "U32");
+}
+
+#[tokio::test]
+async fn test_serde_row() {
+    run_async_test!((repo: Repository<Counter>) => {
+        repo.conn
+            .execute_one(
+                "INSERT INTO counter (strict, lossy) VALUES (?, ?)",
+                vec![1.to_value(), 2.to_value()],
+            )
+            .await
+            .unwrap();
+
+        let row = repo
+            .conn
+            .query_one_map("SELECT * FROM counter", vec![], |row| async move { Ok(row) })
+            .await
+            .unwrap();
+        let json = serde_json::to_string(&row).unwrap();
+        let de = serde_json::from_str::<Row>(&json).unwrap();
+        assert_eq!(de, row.clone());
+        assert_eq!(de.get::<u8>("lossy").unwrap(), 2);
+    });
+}
+
+#[tokio::test]
+async fn test_lossy_column() {
+    run_async_test!((repo: Repository<Counter>) => {
+        repo.conn
+            .execute_one(
+                "INSERT INTO counter (strict, lossy) VALUES (?, ?)",
+                vec![1.to_value(), 300.to_value()],
+            )
+            .await
+            .unwrap();
+
+        let counter = repo.find().filter_model(1).one().await.unwrap();
+        assert_eq!(counter.strict, 1);
+        assert_eq!(counter.lossy, 44);
+
+        repo.conn
+            .execute_one("UPDATE counter SET strict = -1", vec![])
+            .await
+            .unwrap();
+        assert!(repo.find().filter_model(1).one().await.is_err());
+    });
+}
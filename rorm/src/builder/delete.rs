@@ -2,6 +2,8 @@ use std::marker::PhantomData;
 
 use crate::{error::Result, query, Connection, Entity, Model, ToSqlParamPair, Value};
 
+use super::merge_subquery_params;
+
 pub struct DeleteBuilder<E: Entity> {
     sql_builder: query::DeleteBuilder,
     params: Vec<Value>,
@@ -30,8 +32,8 @@ impl<E: Entity> DeleteBuilder<E> {
     }
 
     pub fn filter(mut self, cond: query::Where, params: Vec<Value>) -> Self {
+        self.params = merge_subquery_params(&cond, params);
         self.sql_builder.where_cond(cond);
-        self.params = params;
         self
     }
 
@@ -51,7 +53,7 @@ impl<E: Entity> DeleteBuilder<E> {
     }
 
     pub async fn execute(self, conn: &Connection) -> Result<()> {
-        let pairs = self.to_sql_param_pair()?;
+        let pairs = self.to_sql_param_pair(conn.dialect())?;
         conn.execute_many(pairs).await?;
 
         Ok(())
@@ -59,8 +61,11 @@ impl<E: Entity> DeleteBuilder<E> {
 }
 
 impl<E: Entity> ToSqlParamPair for DeleteBuilder<E> {
-    fn to_sql_param_pair(self) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
-        let sql = self.sql_builder.build()?;
+    fn to_sql_param_pair(
+        mut self,
+        dialect: query::Dialect,
+    ) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
+        let sql = self.sql_builder.dialect(dialect).build()?;
         let params = self.params;
 
         Ok(vec![(sql, vec![params])])
@@ -1,10 +1,24 @@
-use std::marker::PhantomData;
+use std::{collections::HashSet, marker::PhantomData};
 
-use crate::{error::Result, query, Connection, Entity, Model, ToSqlParamPair, Value};
+use crate::{
+    error::Result, query, Connection, Entity, FromRow, FromValue, Model, ToSqlParamPair, ToValue,
+    Value,
+};
+
+use super::{in_list_cond, merge_subquery_params, IN_LIST_CHUNK_SIZE};
 
 pub struct FindBuilder<E: Entity> {
     sql_builder: query::SelectBuilder,
+    join_params: Vec<Value>, // Placed before where params in sql
+    cond: Option<query::Where>,
     params: Vec<Value>,
+    in_list: Option<(String, Vec<Value>)>, // (column, values), ANDed with cond
+    having_params: Vec<Value>,             // Placed after where params in sql
+    is_joined: bool,                       // Entity rows are distinct if joined
+    is_grouped: bool,                      // Group by or having
+    is_distinct: bool,
+    is_ordered: bool,
+    is_limited: bool,
     _marker1: PhantomData<E>,
 }
 
@@ -15,7 +29,16 @@ impl<E: Entity> FindBuilder<E> {
 
         Self {
             sql_builder: builder,
+            join_params: vec![],
+            cond: None,
             params: vec![],
+            in_list: None,
+            having_params: vec![],
+            is_joined: false,
+            is_grouped: false,
+            is_distinct: false,
+            is_ordered: false,
+            is_limited: false,
             _marker1: PhantomData,
         }
     }
@@ -24,37 +47,123 @@ impl<E: Entity> FindBuilder<E> {
     where
         I: Into<E::Model>,
     {
-        let (cond, params) = model.into().gen_where_and_params();
+        let (cond, params) = model.into().gen_qualified_where_and_params(E::INFO.name);
         if let Some(cond) = cond {
-            self.sql_builder.where_cond(cond);
+            self.cond = Some(cond);
             self.params = params;
         }
         self
     }
 
     pub fn filter(mut self, cond: query::Where, params: Vec<Value>) -> Self {
-        self.sql_builder.where_cond(cond);
-        self.params = params;
+        self.params = merge_subquery_params(&cond, params);
+        self.cond = Some(cond);
+        self
+    }
+
+    /// Filter column by values bound as params, ANDed with `filter` and `filter_model`,
+    /// matches nothing if empty, duplicate values are removed. Values more than `IN_LIST_CHUNK_SIZE` are split into multiple
+    /// queries, building fails if they meet order, limit, distinct, join, group, `select` or
+    /// aggregates, which can't be applied to each query
+    pub fn filter_in<S, I, T>(mut self, col: S, values: I) -> Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = T>,
+        T: ToValue,
+    {
+        // Duplicates in different chunks would match the same row twice
+        let mut seen = HashSet::new();
+        let values = values
+            .into_iter()
+            .map(|v| v.to_value())
+            .filter(|v| seen.insert(v.clone()))
+            .collect();
+        self.in_list = Some((col.to_string(), values));
+        self
+    }
+
+    /// Inner join table to filter by its columns, only columns of entity table are selected,
+    /// e.g. `inner_join("post", eq!("post.user_id", "user.id"), vec![])`,
+    /// use `query::alias` to join the same table twice.
+    ///
+    /// Entities are selected by `DISTINCT`, so an entity matching multiple joined rows is
+    /// returned once, mysql requires order keys to be columns of entity then
+    pub fn inner_join<S: ToString>(self, table: S, on: query::Where, params: Vec<Value>) -> Self {
+        self.join(query::JoinKind::Inner, table, Some(on), params)
+    }
+
+    /// Left join table, see `inner_join`
+    pub fn left_join<S: ToString>(self, table: S, on: query::Where, params: Vec<Value>) -> Self {
+        self.join(query::JoinKind::Left, table, Some(on), params)
+    }
+
+    /// Append join, params are the `?` in `on`
+    pub fn join<S: ToString>(
+        mut self,
+        kind: query::JoinKind,
+        table: S,
+        on: Option<query::Where>,
+        params: Vec<Value>,
+    ) -> Self {
+        // Joined columns may override columns of entity with the same name
+        self.sql_builder.columns([format!("{}.*", E::INFO.name)]);
+        let params = match &on {
+            Some(on) => merge_subquery_params(on, params),
+            None => params,
+        };
+        self.sql_builder.join(kind, table, on);
+        self.join_params.extend(params);
+        self.is_joined = true;
         self
     }
 
-    pub fn group_by(mut self, col: &str) -> Self {
+    pub fn group_by<S: Into<query::Expr>>(mut self, col: S) -> Self {
         self.sql_builder.group_by(col);
+        self.is_grouped = true;
+        self
+    }
+
+    /// Filter groups by aggregates, e.g. `having(gt!(count!(), "?"), vec![1.to_value()])`
+    pub fn having(mut self, cond: query::Where, params: Vec<Value>) -> Self {
+        self.having_params = merge_subquery_params(&cond, params);
+        self.sql_builder.having(cond);
+        self.is_grouped = true;
+        self
+    }
+
+    /// Remove duplicate rows, e.g. columns of `select`, entities are already distinct if joined
+    pub fn distinct(mut self) -> Self {
+        self.sql_builder.distinct();
+        self.is_distinct = true;
         self
     }
 
-    pub fn order_by(mut self, col: &str, is_asc: bool) -> Self {
+    /// Column or expression, e.g. `json_extract!("profile", "$.age")`
+    pub fn order_by<S: Into<query::Expr>>(mut self, col: S, is_asc: bool) -> Self {
         self.sql_builder.order_by(col, is_asc);
+        self.is_ordered = true;
         self
     }
 
     pub fn limit(mut self, limit: u64, offset: u64) -> Self {
         self.sql_builder.limit(limit, offset);
+        self.is_limited = true;
         self
     }
 
+    /// Convert to subquery selecting column, e.g. `exists!(sub)`, `in_select!("id", sub)`,
+    /// params are bound to subquery and merged into params of outer query at its position,
+    /// in list is not split
+    pub fn subquery<S: Into<query::Expr>>(mut self, column: S) -> query::SelectBuilder {
+        self.sql_builder.columns([column]);
+        let params = self.set_where_cond();
+        self.sql_builder.bind_params(params);
+
+        self.sql_builder
+    }
+
     pub async fn execute(self, conn: &Connection) -> Result<Vec<E>> {
-        let pairs = self.to_sql_param_pair()?;
+        let pairs = self.to_sql_param_pair(conn.dialect())?;
         let mut list = vec![];
         for (sql, params_list) in pairs {
             for params in params_list {
@@ -69,13 +178,170 @@ impl<E: Entity> FindBuilder<E> {
 
         Ok(list)
     }
+
+    /// Select columns instead of entity, e.g. group keys and aggregates,
+    /// columns are read by position into tuple or by name (alias) into type implementing `FromRow`
+    pub async fn select<T, I, S>(mut self, conn: &Connection, columns: I) -> Result<Vec<T>>
+    where
+        T: FromRow,
+        I: IntoIterator<Item = S>,
+        S: Into<query::Expr>,
+    {
+        self.sql_builder.columns(columns);
+        let (sql, params) = self.build_one(conn.dialect(), "Select")?;
+
+        conn.query_many_map(&sql, params, |row| async move { T::from_row(&row) })
+            .await
+    }
+
+    /// Count matched rows, groups if grouped, or distinct rows if distinct or joined
+    pub async fn count(mut self, conn: &Connection) -> Result<u64> {
+        let is_distinct = self.is_distinct || self.is_joined;
+        // Groups and distinct rows are counted from the query as derived table
+        let is_derived = is_distinct || self.is_grouped;
+        if self.is_joined {
+            self.sql_builder.distinct();
+        }
+        if !is_derived {
+            self.sql_builder
+                .columns([query::alias(query::count!(), "agg")]);
+        } else if !is_distinct {
+            self.sql_builder.columns(["1"]);
+        }
+
+        let mut count = 0;
+        for (sql, params) in self.build(conn.dialect())? {
+            let sql = if is_derived {
+                let mut builder =
+                    query::SelectBuilder::new(query::alias(format!("({})", sql.trim_end()), "t"));
+                builder.columns([query::alias(query::count!(), "agg")]);
+                builder.build()?
+            } else {
+                sql
+            };
+            count += conn
+                .query_one_map(&sql, params, |row| async move { row.get::<u64>("agg") })
+                .await?;
+        }
+
+        Ok(count)
+    }
+
+    /// Whether any row is matched
+    pub async fn exists(mut self, conn: &Connection) -> Result<bool> {
+        self.sql_builder.columns(["1"]).limit(1, 0);
+        for (sql, params) in self.build(conn.dialect())? {
+            let rows = conn
+                .query_many_map(&sql, params, |_| async move { Ok(()) })
+                .await?;
+            if !rows.is_empty() {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Query single value of aggregate, result of `sum`, `avg`, `min` and `max` is null if
+    /// no row is matched, so `T` should be `Option` unless rows are known to exist
+    pub async fn aggregate<T>(mut self, conn: &Connection, agg: impl Into<query::Expr>) -> Result<T>
+    where
+        T: FromValue<Output = T>,
+    {
+        self.sql_builder.columns([agg.into().alias("agg")]);
+        let (sql, params) = self.build_one(conn.dialect(), "Aggregate")?;
+
+        conn.query_one_map(&sql, params, |row| async move { row.get::<T>("agg") })
+            .await
+    }
+
+    /// Sql and params of the query, fails if in list is split
+    fn build_one(self, dialect: query::Dialect, name: &str) -> Result<(String, Vec<Value>)> {
+        let mut pairs = self.build(dialect)?;
+        if pairs.len() != 1 {
+            return Err(crate::error::query_builder!(
+                "{} of in list longer than {} is not supported",
+                name,
+                IN_LIST_CHUNK_SIZE
+            ));
+        }
+
+        Ok(pairs.remove(0))
+    }
+
+    /// Sql and params of each query, in list is split into chunks
+    fn build(mut self, dialect: query::Dialect) -> Result<Vec<(String, Vec<Value>)>> {
+        self.sql_builder.dialect(dialect);
+        let chunks = match self.in_list.take() {
+            Some((col, values)) if values.len() > IN_LIST_CHUNK_SIZE => values
+                .chunks(IN_LIST_CHUNK_SIZE)
+                .map(|chunk| Some((col.clone(), chunk.to_vec())))
+                .collect(),
+            in_list => vec![in_list],
+        };
+        if chunks.len() > 1 {
+            let clauses = [
+                (self.is_ordered, "order_by"),
+                (self.is_limited, "limit"),
+                (self.is_distinct, "distinct"),
+                (self.is_joined, "join"),
+                (self.is_grouped, "group_by or having"),
+            ];
+            if let Some((_, clause)) = clauses.iter().find(|(is_set, _)| *is_set) {
+                return Err(crate::error::query_builder!(
+                    "`{}` of in list longer than {} is not supported",
+                    clause,
+                    IN_LIST_CHUNK_SIZE
+                ));
+            }
+        }
+
+        let mut pairs = vec![];
+        for chunk in chunks {
+            self.in_list = chunk;
+            let params = self.set_where_cond();
+            pairs.push((self.sql_builder.build()?, params));
+        }
+
+        Ok(pairs)
+    }
+
+    /// Set where of sql builder by cond and in list, return params placed as in sql,
+    /// join, where, then having
+    fn set_where_cond(&mut self) -> Vec<Value> {
+        let mut params = self.join_params.clone();
+        params.extend(self.params.iter().cloned());
+
+        let in_cond = self.in_list.as_ref().map(|(col, values)| {
+            params.extend(values.iter().cloned());
+            in_list_cond(col, values.len())
+        });
+        let cond = match (self.cond.clone(), in_cond) {
+            (Some(cond), Some(in_cond)) => Some(query::and!(cond, in_cond)),
+            (cond, in_cond) => cond.or(in_cond),
+        };
+        if let Some(cond) = cond {
+            self.sql_builder.where_cond(cond);
+        }
+
+        params.extend(self.having_params.iter().cloned());
+        params
+    }
 }
 
 impl<E: Entity> ToSqlParamPair for FindBuilder<E> {
-    fn to_sql_param_pair(self) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
-        let sql = self.sql_builder.build()?;
-        let params = self.params;
+    fn to_sql_param_pair(
+        mut self,
+        dialect: query::Dialect,
+    ) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
+        if self.is_joined {
+            self.sql_builder.distinct();
+        }
 
-        Ok(vec![(sql, vec![params])])
+        Ok(self
+            .build(dialect)?
+            .into_iter()
+            .map(|(sql, params)| (sql, vec![params]))
+            .collect())
     }
 }
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use rorm_query::QueryBuilder;
+use rorm_query::{Dialect, QueryBuilder};
 
 use crate::{error::Result, Connection, Entity, Model, Value};
 
@@ -8,6 +8,7 @@ use super::ToSqlParamPair;
 
 pub struct InsertBuilder<E: Entity> {
     pairs: Vec<(Vec<&'static str>, Vec<Vec<Value>>)>, // (cols, params_list)
+    keys: Vec<Option<E::PrimaryKey>>,                 // Primary keys generated by client
     _marker1: PhantomData<E>,
 }
 
@@ -15,6 +16,7 @@ impl<E: Entity> InsertBuilder<E> {
     pub fn new() -> Self {
         Self {
             pairs: vec![],
+            keys: vec![],
             _marker1: PhantomData,
         }
     }
@@ -23,7 +25,9 @@ impl<E: Entity> InsertBuilder<E> {
     where
         I: Into<E::Model>,
     {
-        let pairs = model.into().into_set_pairs();
+        let mut model = model.into();
+        self.keys.push(model.gen_primary_key());
+        let pairs = model.into_set_pairs();
         let (cols, params) =
             pairs
                 .into_iter()
@@ -59,19 +63,33 @@ impl<E: Entity> InsertBuilder<E> {
             .fold(self, |this, model| this.model(model))
     }
 
-    pub async fn execute(self, conn: &Connection) -> Result<Vec<E::PrimaryKey>> {
-        let pairs = self.to_sql_param_pair()?;
+    pub async fn execute(mut self, conn: &Connection) -> Result<Vec<E::PrimaryKey>> {
+        let keys = std::mem::take(&mut self.keys);
+        let pairs = self.to_sql_param_pair(conn.dialect())?;
         let ids = conn.execute_many(pairs).await?;
 
-        Ok(ids
-            .into_iter()
-            .map(|id| E::Model::to_primary_key(id))
-            .collect())
+        ids.into_iter()
+            .zip(keys)
+            .map(|(id, key)| {
+                key.or_else(|| E::Model::to_primary_key(id)).ok_or_else(|| {
+                    crate::error::from_value!(
+                        "Inserted id {} can't be converted to primary key of {}",
+                        id,
+                        E::INFO.name
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// (cols, params_list) grouped by columns
+    pub(crate) fn into_pairs(self) -> Vec<(Vec<&'static str>, Vec<Vec<Value>>)> {
+        self.pairs
     }
 }
 
 impl<E: Entity> ToSqlParamPair for InsertBuilder<E> {
-    fn to_sql_param_pair(self) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
+    fn to_sql_param_pair(self, dialect: Dialect) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
         let mut list = vec![];
 
         for (cols, params_list) in self.pairs {
@@ -79,6 +97,7 @@ impl<E: Entity> ToSqlParamPair for InsertBuilder<E> {
             let sql = QueryBuilder::insert(E::INFO.name)
                 .columns(cols)
                 .values(values)
+                .dialect(dialect)
                 .build()?;
             list.push((sql, params_list));
         }
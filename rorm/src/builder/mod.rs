@@ -2,14 +2,52 @@ mod delete;
 mod find;
 mod insert;
 mod update;
+mod upsert;
 
 pub use delete::DeleteBuilder;
 pub use find::FindBuilder;
 pub use insert::InsertBuilder;
 pub use update::UpdateBuilder;
+pub use upsert::UpsertBuilder;
 
-use crate::{error::Result, Value};
+use crate::{error::Result, query, ToValue, Value};
+
+/// Max values of in list bound in one query, lower than 999, the default limit of params of
+/// sqlite before 3.32, so other params of the query can still be bound
+pub const IN_LIST_CHUNK_SIZE: usize = 500;
 
 pub trait ToSqlParamPair {
-    fn to_sql_param_pair(self) -> Result<Vec<(String, Vec<Vec<Value>>)>>;
+    /// Sql rendered by dialect with params list
+    fn to_sql_param_pair(self, dialect: query::Dialect) -> Result<Vec<(String, Vec<Vec<Value>>)>>;
+}
+
+/// `col IN (?, ?, ..)` with each value bound as param, `1 = 0` if values is empty,
+/// e.g. `let (cond, params) = in_list("id", ids)`
+pub fn in_list<S, I, T>(col: S, values: I) -> (query::Where, Vec<Value>)
+where
+    S: ToString,
+    I: IntoIterator<Item = T>,
+    T: ToValue,
+{
+    let params = values.into_iter().map(|v| v.to_value()).collect::<Vec<_>>();
+
+    (in_list_cond(col.to_string(), params.len()), params)
+}
+
+pub(crate) fn in_list_cond<S: ToString>(col: S, len: usize) -> query::Where {
+    query::Where::In(
+        Box::new(query::literal!(col.to_string())),
+        (0..len).map(|_| query::literal!("?")).collect(),
+    )
+}
+
+/// Insert params bound by subqueries of cond at the position of each subquery
+pub(crate) fn merge_subquery_params(cond: &query::Where, mut params: Vec<Value>) -> Vec<Value> {
+    // From the last, so positions of former subqueries are kept
+    for (pos, sub_params) in cond.subquery_params::<Vec<Value>>().into_iter().rev() {
+        let pos = pos.min(params.len());
+        params.splice(pos..pos, sub_params.iter().cloned());
+    }
+
+    params
 }
@@ -1,6 +1,10 @@
 use std::marker::PhantomData;
 
-use crate::{error::Result, query, Connection, Entity, Model, ToSqlParamPair, Value};
+use crate::{
+    error::Result, query, ColumnType, Connection, Entity, Model, ToSqlParamPair, ToValue, Value,
+};
+
+use super::merge_subquery_params;
 
 pub struct UpdateBuilder<E: Entity> {
     sql_builder: query::UpdateBuilder,
@@ -24,14 +28,37 @@ impl<E: Entity> UpdateBuilder<E> {
         I: Into<E::Model>,
     {
         let pairs = model.into().into_set_pairs();
-        let mut params = vec![];
 
         for (col, param) in pairs {
             self.sql_builder.set(col, "?".into());
-            params.push(param);
+            self.set_params.push(param);
         }
 
-        self.set_params = params;
+        self
+    }
+
+    /// Set column by expression, e.g. `set("nick", "COALESCE(nick, ?)".into(), vec![name])`,
+    /// params are bound in order of `set`, `set_model` and `increment`
+    pub fn set(mut self, col: &str, expr: query::QueryValue, params: Vec<Value>) -> Self {
+        self.sql_builder.set(col, expr);
+        self.set_params.extend(params);
+        self
+    }
+
+    /// `col = col + by` without read-modify-write race, decrease by negative value,
+    /// u64 column above i64::MAX fails in sqlite instead of being treated as 0
+    pub fn increment<V: ToValue>(mut self, col: &str, by: V) -> Self {
+        let is_u64 = E::INFO
+            .columns
+            .iter()
+            .any(|c| c.name == col && c.ty == ColumnType::U64);
+        if is_u64 {
+            let expr = query::Expr::u64_number(col) + "?";
+            self.sql_builder.set(col, expr.into());
+        } else {
+            self.sql_builder.increment(col, "?".into());
+        }
+        self.set_params.push(by.to_value());
         self
     }
 
@@ -48,8 +75,8 @@ impl<E: Entity> UpdateBuilder<E> {
     }
 
     pub fn filter(mut self, cond: query::Where, params: Vec<Value>) -> Self {
+        self.where_params = merge_subquery_params(&cond, params);
         self.sql_builder.where_cond(cond);
-        self.where_params = params;
         self
     }
 
@@ -69,7 +96,7 @@ impl<E: Entity> UpdateBuilder<E> {
     }
 
     pub async fn execute(self, conn: &Connection) -> Result<()> {
-        let pairs = self.to_sql_param_pair()?;
+        let pairs = self.to_sql_param_pair(conn.dialect())?;
         conn.execute_many(pairs).await?;
 
         Ok(())
@@ -77,8 +104,11 @@ impl<E: Entity> UpdateBuilder<E> {
 }
 
 impl<E: Entity> ToSqlParamPair for UpdateBuilder<E> {
-    fn to_sql_param_pair(self) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
-        let sql = self.sql_builder.build()?;
+    fn to_sql_param_pair(
+        mut self,
+        dialect: query::Dialect,
+    ) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
+        let sql = self.sql_builder.dialect(dialect).build()?;
         let mut params = self.set_params;
         params.extend(self.where_params);
 
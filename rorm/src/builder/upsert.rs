@@ -0,0 +1,139 @@
+use std::marker::PhantomData;
+
+use crate::{
+    error::Result,
+    query::{self, Dialect, UpsertAction},
+    Connection, Entity, InsertBuilder, Value,
+};
+
+/// Insert models, or update existing rows conflicting on target, which is primary key by default.
+/// Every inserted column except target is updated if neither `update`, `set` nor `do_nothing`
+/// is called.
+pub struct UpsertBuilder<E: Entity> {
+    insert: InsertBuilder<E>,
+    target: Option<Vec<String>>,
+    actions: Option<Vec<(String, UpsertAction)>>, // Some(vec![]) is do nothing
+    action_params: Vec<Value>,
+    _marker1: PhantomData<E>,
+}
+
+impl<E: Entity> UpsertBuilder<E> {
+    pub fn new() -> Self {
+        Self {
+            insert: InsertBuilder::new(),
+            target: None,
+            actions: None,
+            action_params: vec![],
+            _marker1: PhantomData,
+        }
+    }
+
+    pub fn model<I>(mut self, model: I) -> Self
+    where
+        I: Into<E::Model>,
+    {
+        self.insert = self.insert.model(model);
+        self
+    }
+
+    pub fn models<L, I>(mut self, models: L) -> Self
+    where
+        L: IntoIterator<Item = I>,
+        I: Into<E::Model>,
+    {
+        self.insert = self.insert.models(models);
+        self
+    }
+
+    /// Columns of primary key or unique index, mysql ignores it and reacts to any unique key
+    pub fn on_conflict<I, S>(mut self, target: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.target = Some(target.into_iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// Update columns to the inserted values
+    pub fn update<I, S>(mut self, cols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.actions.get_or_insert_with(Vec::new).extend(
+            cols.into_iter()
+                .map(|col| (col.to_string(), UpsertAction::Excluded)),
+        );
+        self
+    }
+
+    /// Update column by expression of existing row, e.g. `set("count", "count + ?".into(), vec![n])`,
+    /// params are bound after inserted values
+    pub fn set(mut self, col: &str, expr: query::QueryValue, params: Vec<Value>) -> Self {
+        self.actions
+            .get_or_insert_with(Vec::new)
+            .push((col.to_string(), UpsertAction::Value(expr)));
+        self.action_params.extend(params);
+        self
+    }
+
+    /// Keep existing rows
+    pub fn do_nothing(mut self) -> Self {
+        self.actions = Some(vec![]);
+        self
+    }
+
+    /// Primary keys are not returned, they are not reported for updated rows
+    pub async fn execute(self, conn: &Connection) -> Result<()> {
+        let pairs = self.build(conn.dialect())?;
+        conn.execute_many(pairs).await?;
+
+        Ok(())
+    }
+
+    fn build(self, dialect: Dialect) -> Result<Vec<(String, Vec<Vec<Value>>)>> {
+        let target = self.target.unwrap_or_else(|| {
+            E::INFO
+                .columns
+                .iter()
+                .filter(|col| col.is_primary_key)
+                .map(|col| col.name.to_string())
+                .collect()
+        });
+
+        let mut list = vec![];
+        for (cols, mut params_list) in self.insert.into_pairs() {
+            let mut builder = query::QueryBuilder::insert(E::INFO.name);
+            builder
+                .columns(&cols)
+                .values(cols.iter().map(|_| "?".into()))
+                .on_conflict(&target)
+                .dialect(dialect);
+
+            let actions = self.actions.clone().unwrap_or_else(|| {
+                cols.iter()
+                    .filter(|col| !target.iter().any(|t| t == *col))
+                    .map(|col| (col.to_string(), UpsertAction::Excluded))
+                    .collect()
+            });
+            for (col, action) in actions {
+                builder.do_update(col, action);
+            }
+
+            for params in &mut params_list {
+                params.extend(self.action_params.iter().cloned());
+            }
+
+            list.push((builder.build()?, params_list));
+        }
+
+        Ok(list)
+    }
+}
+
+impl<E: Entity> Default for UpsertBuilder<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
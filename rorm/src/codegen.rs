@@ -0,0 +1,263 @@
+//! # Codegen
+//!
+//! Generate entity structs from the schema of a live database. Tables referenced by
+//! a single column foreign key get a `Vec` relation field of the referencing table.
+
+use crate::{
+    error::{self, Result},
+    migration::MIGRATION_TABLES,
+    ColumnDesc, ColumnType, Connection, ForeignKeyDesc, TableDesc,
+};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Read tables from database and generate rust source of entities, all tables except the ones
+/// of migrator are generated if `tables` is empty
+pub async fn gen_entities(conn: &Connection, tables: &[String]) -> Result<String> {
+    let names = if tables.is_empty() {
+        conn.list_tables()
+            .await?
+            .into_iter()
+            .filter(|name| !MIGRATION_TABLES.contains(&name.as_str()))
+            .collect()
+    } else {
+        tables.to_vec()
+    };
+
+    let mut schema = vec![];
+    for name in names {
+        let desc = conn
+            .describe_table(&name)
+            .await?
+            .ok_or_else(|| error::argument!("Table `{}` not found", name))?;
+        let foreign_keys = conn.list_foreign_keys(&name).await?;
+        schema.push((desc, foreign_keys));
+    }
+
+    Ok(gen_entities_from_desc(&schema))
+}
+
+/// Generate rust source of entities from tables and their foreign keys
+pub fn gen_entities_from_desc(schema: &[(TableDesc, Vec<ForeignKeyDesc>)]) -> String {
+    let mut src = String::from("use rorm::Entity;\n");
+    for (desc, _) in schema {
+        src.push('\n');
+        src.push_str(&gen_entity(desc, schema));
+    }
+
+    src
+}
+
+fn gen_entity(desc: &TableDesc, schema: &[(TableDesc, Vec<ForeignKeyDesc>)]) -> String {
+    let mut lines = vec![
+        "#[derive(Debug, Entity)]".to_string(),
+        format!("#[rorm(table_name = {:?})]", desc.name),
+    ];
+    for idx in &desc.indexes {
+        let keys = idx
+            .keys
+            .iter()
+            .map(|key| field_name(key))
+            .collect::<Vec<_>>();
+        let mut line = format!("#[rorm(index = [{}])]", keys.join(", "));
+        if idx.is_unique {
+            line.push_str(&format!(" // FIXME: `{}` is unique", idx.name));
+        }
+        lines.push(line);
+    }
+    lines.push(format!("pub struct {} {{", struct_name(&desc.name)));
+
+    // Columns
+    let mut field_names = vec![];
+    for col in &desc.columns {
+        lines.extend(gen_field(col));
+        field_names.push(field_name(&col.name));
+    }
+
+    // Relations of tables referencing self
+    for (other, foreign_keys) in schema {
+        for fk in foreign_keys.iter().filter(|fk| fk.ref_table == desc.name) {
+            if other.name == desc.name {
+                lines.push(format!(
+                    "    // Relation of `{}` to self is skipped, loading it would recurse",
+                    fk.columns.join(", ")
+                ));
+                continue;
+            }
+            if fk.columns.len() != 1 {
+                lines.push(format!(
+                    "    // FIXME: Relation of `{}` ({}) has multiple columns",
+                    other.name,
+                    fk.columns.join(", ")
+                ));
+                continue;
+            }
+
+            let mut name = if other.name.ends_with('s') {
+                format!("{}_list", other.name)
+            } else {
+                format!("{}s", other.name)
+            };
+            if field_names.contains(&name) {
+                name = format!("{}_by_{}", name, fk.columns[0]);
+            }
+            lines.push(format!(
+                "    #[rorm(relation = {} > {})]",
+                field_name(&fk.ref_columns[0]),
+                field_name(&fk.columns[0])
+            ));
+            lines.push(format!(
+                "    pub {}: Vec<{}>,",
+                name,
+                struct_name(&other.name)
+            ));
+            field_names.push(name);
+        }
+    }
+    lines.push("}".into());
+
+    lines.join("\n") + "\n"
+}
+
+fn gen_field(col: &ColumnDesc) -> Vec<String> {
+    let mut lines = vec![];
+    let mut attrs = vec![];
+
+    let name = field_name(&col.name);
+    if name != col.name {
+        attrs.push(format!("column = {:?}", col.name));
+    }
+    if col.is_primary_key {
+        attrs.push("primary_key".to_string());
+    }
+    if col.is_auto_increment {
+        attrs.push("auto_increment".into());
+    }
+    match &col.ty {
+        ColumnType::Str(len) | ColumnType::Bytes(len) if *len != 65535 => {
+            attrs.push(format!("length = {}", len))
+        }
+        ColumnType::Decimal(precision, scale) if (*precision, *scale) != (38, 10) => {
+            attrs.push(format!("precision = {}, scale = {}", precision, scale))
+        }
+        ColumnType::UuidText => attrs.push("uuid = text".into()),
+        ColumnType::Enum(variants) => lines.push(format!(
+            "    // FIXME: Enum of {}, can be replaced by a DbEnum",
+            variants
+                .iter()
+                .map(|v| format!("{:?}", v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        _ => {}
+    }
+    if let Some(def) = &col.default {
+        match gen_default(def) {
+            Some(def) => attrs.push(format!("default = {}", def)),
+            None => lines.push(format!("    // FIXME: Default `{}` is not a literal", def)),
+        }
+    }
+    if col.is_unique {
+        attrs.push("unique".into());
+    }
+
+    if !attrs.is_empty() {
+        lines.push(format!("    #[rorm({})]", attrs.join(", ")));
+    }
+    let ty = rust_type(&col.ty);
+    if col.is_not_null || col.is_primary_key {
+        lines.push(format!("    pub {}: {},", name, ty));
+    } else {
+        lines.push(format!("    pub {}: Option<{}>,", name, ty));
+    }
+
+    lines
+}
+
+/// Convert sql literal to the literal accepted by `#[rorm(default = ..)]`,
+/// e.g. `'abc'` => `"abc"`, `18` => `18`
+fn gen_default(def: &str) -> Option<String> {
+    if def.len() >= 2 && def.starts_with('\'') && def.ends_with('\'') {
+        // Inner quotes are kept escaped, macro puts the string in quotes as is
+        return Some(format!("{:?}", &def[1..def.len() - 1]));
+    }
+
+    let is_number = def.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && def.chars().filter(|c| *c == '.').count() <= 1
+        && def.starts_with(|c: char| c.is_ascii_digit())
+        && def.ends_with(|c: char| c.is_ascii_digit());
+    if is_number {
+        Some(def.into())
+    } else {
+        None
+    }
+}
+
+fn rust_type(ty: &ColumnType) -> &'static str {
+    match ty {
+        ColumnType::Bool => "bool",
+        ColumnType::I8 => "i8",
+        ColumnType::U8 => "u8",
+        ColumnType::I16 => "i16",
+        ColumnType::U16 => "u16",
+        ColumnType::I32 => "i32",
+        ColumnType::U32 => "u32",
+        ColumnType::I64 => "i64",
+        ColumnType::U64 => "u64",
+        ColumnType::F32 => "f32",
+        ColumnType::F64 => "f64",
+        ColumnType::Str(_) => "String",
+        ColumnType::Bytes(_) => "Vec<u8>",
+        ColumnType::Date => "rorm::Date",
+        ColumnType::Time => "rorm::Time",
+        ColumnType::DateTime => "rorm::DateTime",
+        ColumnType::Timestamp => "rorm::Timestamp",
+        ColumnType::Decimal(_, _) => "rorm::Decimal",
+        ColumnType::Uuid | ColumnType::UuidText => "uuid::Uuid",
+        ColumnType::Json => "serde_json::Value",
+        ColumnType::Enum(_) => "String",
+    }
+}
+
+/// `user_address` => `UserAddress`
+fn struct_name(table_name: &str) -> String {
+    let name = table_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Table{}", name)
+    }
+}
+
+/// Field name of column, the column name is kept by `#[rorm(column = ..)]` if they differ,
+/// e.g. `type` => `type_`, `first name` => `first_name`
+fn field_name(column: &str) -> String {
+    let mut name = column
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name = format!("col_{}", name);
+    }
+    if KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+
+    name
+}
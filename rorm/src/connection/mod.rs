@@ -4,7 +4,10 @@ use std::future::Future;
 
 use rorm_conn::Connection as InternalConn;
 
-use crate::{error::Result, Entity, Repository, Row, TableInfo, Value};
+use crate::{
+    error::Result, query::Dialect, Entity, EntityList, ForeignKeyDesc, Repository, Row,
+    SchemaChange, SchemaDiff, SyncMode, TableDesc, TableInfo, Value,
+};
 
 use transaction::Transaction;
 
@@ -13,6 +16,13 @@ pub struct Connection {
     internal: InternalConn,
 }
 
+impl From<InternalConn> for Connection {
+    #[inline]
+    fn from(internal: InternalConn) -> Self {
+        Self { internal }
+    }
+}
+
 impl Connection {
     #[inline]
     pub async fn connect(url: &str) -> Result<Self> {
@@ -71,6 +81,73 @@ impl Connection {
         self.internal.init_table(info).await
     }
 
+    #[inline]
+    pub async fn list_tables(&self) -> Result<Vec<String>> {
+        self.internal.list_tables().await
+    }
+
+    #[inline]
+    pub async fn describe_table(&self, name: &str) -> Result<Option<TableDesc>> {
+        self.internal.describe_table(name).await
+    }
+
+    #[inline]
+    pub async fn list_foreign_keys(&self, table_name: &str) -> Result<Vec<ForeignKeyDesc>> {
+        self.internal.list_foreign_keys(table_name).await
+    }
+
+    #[inline]
+    pub async fn sync_table(&self, info: &TableInfo, mode: SyncMode) -> Result<SchemaDiff> {
+        self.internal.sync_table(info, mode).await
+    }
+
+    #[inline]
+    pub async fn drop_table(&self, name: &str) -> Result<()> {
+        self.internal.drop_table(name).await
+    }
+
+    #[inline]
+    pub async fn drop_table_if_exists(&self, name: &str) -> Result<()> {
+        self.internal.drop_table_if_exists(name).await
+    }
+
+    #[inline]
+    pub async fn truncate_table(&self, name: &str) -> Result<()> {
+        self.internal.truncate_table(name).await
+    }
+
+    #[inline]
+    pub async fn rename_table(&self, from: &str, to: &str) -> Result<()> {
+        self.internal.rename_table(from, to).await
+    }
+
+    /// Init tables of entities in order, e.g. `conn.init_all::<(User, Address)>()`
+    #[inline]
+    pub async fn init_all<L: EntityList>(&self) -> Result<()> {
+        L::init_all(self).await
+    }
+
+    /// Drop tables of entities if exist in reverse order
+    #[inline]
+    pub async fn drop_all<L: EntityList>(&self) -> Result<()> {
+        L::drop_all(self).await
+    }
+
+    #[inline]
+    pub async fn diff_table(&self, expected: &TableDesc) -> Result<Vec<SchemaChange>> {
+        self.internal.diff_table(expected).await
+    }
+
+    #[inline]
+    pub fn gen_ddl(&self, desc: &TableDesc) -> Vec<String> {
+        self.internal.gen_ddl(desc)
+    }
+
+    #[inline]
+    pub fn dialect(&self) -> Dialect {
+        self.internal.dialect()
+    }
+
     #[inline]
     pub fn repository<E: Entity>(&self) -> Repository<E> {
         Repository::new(self.clone())
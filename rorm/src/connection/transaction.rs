@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 
 use crate::{
-    error::Result, query::Where, Connection, DeleteBuilder, Entity, InsertBuilder, ToSqlParamPair,
-    UpdateBuilder, Value,
+    error::Result,
+    query::{Dialect, Where},
+    Connection, DeleteBuilder, Entity, InsertBuilder, ToSqlParamPair, UpdateBuilder, Value,
 };
 
 pub struct Transaction<'conn> {
@@ -19,7 +20,7 @@ impl<'conn> Transaction<'conn> {
     }
 
     pub fn repository<E: Entity>(&mut self) -> RepoTransaction<'_, E> {
-        RepoTransaction::new(&mut self.pairs)
+        RepoTransaction::new(&mut self.pairs, self.conn.dialect())
     }
 
     pub async fn commit(self) -> Result<()> {
@@ -35,39 +36,43 @@ impl<'conn> Transaction<'conn> {
 
 pub struct RepoTransaction<'pair, E: Entity> {
     pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>,
+    dialect: Dialect,
     _marker: PhantomData<E>,
 }
 
 impl<'conn, 'pair, E: Entity> RepoTransaction<'pair, E> {
-    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>) -> Self {
+    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>, dialect: Dialect) -> Self {
         Self {
             pairs,
+            dialect,
             _marker: PhantomData,
         }
     }
 
     pub fn insert(&mut self) -> TransInsertBuilder<'_, E> {
-        TransInsertBuilder::new(self.pairs)
+        TransInsertBuilder::new(self.pairs, self.dialect)
     }
 
     pub fn delete(&mut self) -> TransDeleteBuilder<'_, E> {
-        TransDeleteBuilder::new(self.pairs)
+        TransDeleteBuilder::new(self.pairs, self.dialect)
     }
 
     pub fn update(&mut self) -> TransUpdateBuilder<'_, E> {
-        TransUpdateBuilder::new(self.pairs)
+        TransUpdateBuilder::new(self.pairs, self.dialect)
     }
 }
 
 pub struct TransInsertBuilder<'pair, E: Entity> {
     pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>,
+    dialect: Dialect,
     builder: InsertBuilder<E>,
 }
 
 impl<'conn, 'pair, E: Entity> TransInsertBuilder<'pair, E> {
-    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>) -> Self {
+    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>, dialect: Dialect) -> Self {
         Self {
             pairs,
+            dialect,
             builder: InsertBuilder::new(),
         }
     }
@@ -90,25 +95,29 @@ impl<'conn, 'pair, E: Entity> TransInsertBuilder<'pair, E> {
     }
 
     pub async fn one(self) -> Result<()> {
-        self.pairs.extend(self.builder.to_sql_param_pair()?);
+        self.pairs
+            .extend(self.builder.to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 
     pub async fn all(self) -> Result<()> {
-        self.pairs.extend(self.builder.to_sql_param_pair()?);
+        self.pairs
+            .extend(self.builder.to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 }
 
 pub struct TransDeleteBuilder<'pair, E: Entity> {
     pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>,
+    dialect: Dialect,
     builder: DeleteBuilder<E>,
 }
 
 impl<'conn, 'pair, E: Entity> TransDeleteBuilder<'pair, E> {
-    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>) -> Self {
+    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>, dialect: Dialect) -> Self {
         Self {
             pairs,
+            dialect,
             builder: DeleteBuilder::new(),
         }
     }
@@ -137,32 +146,38 @@ impl<'conn, 'pair, E: Entity> TransDeleteBuilder<'pair, E> {
     }
 
     pub async fn limit(self, limit: u64, offset: u64) -> Result<()> {
-        self.pairs
-            .extend(self.builder.limit(limit, offset).to_sql_param_pair()?);
+        self.pairs.extend(
+            self.builder
+                .limit(limit, offset)
+                .to_sql_param_pair(self.dialect)?,
+        );
         Ok(())
     }
 
     pub async fn one(self) -> Result<()> {
         self.pairs
-            .extend(self.builder.limit(1, 0).to_sql_param_pair()?);
+            .extend(self.builder.limit(1, 0).to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 
     pub async fn all(self) -> Result<()> {
-        self.pairs.extend(self.builder.to_sql_param_pair()?);
+        self.pairs
+            .extend(self.builder.to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 }
 
 pub struct TransUpdateBuilder<'pair, E: Entity> {
     pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>,
+    dialect: Dialect,
     builder: UpdateBuilder<E>,
 }
 
 impl<'conn, 'pair, E: Entity> TransUpdateBuilder<'pair, E> {
-    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>) -> Self {
+    pub fn new(pairs: &'pair mut Vec<(String, Vec<Vec<Value>>)>, dialect: Dialect) -> Self {
         Self {
             pairs,
+            dialect,
             builder: UpdateBuilder::new(),
         }
     }
@@ -199,19 +214,23 @@ impl<'conn, 'pair, E: Entity> TransUpdateBuilder<'pair, E> {
     }
 
     pub async fn limit(self, limit: u64, offset: u64) -> Result<()> {
-        self.pairs
-            .extend(self.builder.limit(limit, offset).to_sql_param_pair()?);
+        self.pairs.extend(
+            self.builder
+                .limit(limit, offset)
+                .to_sql_param_pair(self.dialect)?,
+        );
         Ok(())
     }
 
     pub async fn one(self) -> Result<()> {
         self.pairs
-            .extend(self.builder.limit(1, 0).to_sql_param_pair()?);
+            .extend(self.builder.limit(1, 0).to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 
     pub async fn all(self) -> Result<()> {
-        self.pairs.extend(self.builder.to_sql_param_pair()?);
+        self.pairs
+            .extend(self.builder.to_sql_param_pair(self.dialect)?);
         Ok(())
     }
 }
@@ -0,0 +1,10 @@
+use crate::{ColumnType, DbType};
+
+/// Unit enum stored in a single column, implemented by `#[derive(DbEnum)]`
+///
+/// Deprecated, use `DbType` which is implemented by the derive too. It is not marked by
+/// `#[deprecated]`, importing the derive would warn as they share the name
+pub trait DbEnum: DbType {
+    /// Same as `DbType::COLUMN_TYPE`
+    const COLUMN_TYPE: ColumnType = <Self as DbType>::COLUMN_TYPE;
+}
@@ -1,6 +1,6 @@
 use crate::{
-    error::Result, Connection, DeleteBuilder, FindBuilder, InsertBuilder, Model, Row, TableInfo,
-    UpdateBuilder,
+    error::Result, Connection, DeleteBuilder, FindBuilder, InsertBuilder, Model, Row, SchemaDiff,
+    SyncMode, TableInfo, UpdateBuilder, UpsertBuilder,
 };
 
 #[async_trait::async_trait]
@@ -19,11 +19,42 @@ pub trait Entity: Sized + Send {
         Ok(conn.init_table(&Self::INFO).await?)
     }
 
+    /// Init table, then add missing columns and indexes, type changes and drops
+    /// are only applied in `SyncMode::Destructive`
+    async fn init_with(conn: &Connection, mode: SyncMode) -> Result<SchemaDiff> {
+        Ok(conn.sync_table(&Self::INFO, mode).await?)
+    }
+
+    /// Drop table, return error if table not exists
+    async fn drop_table(conn: &Connection) -> Result<()> {
+        conn.drop_table(Self::INFO.name).await
+    }
+
+    /// Drop table if exists
+    async fn drop_if_exists(conn: &Connection) -> Result<()> {
+        conn.drop_table_if_exists(Self::INFO.name).await
+    }
+
+    /// Delete all rows and reset auto increment counter
+    async fn truncate(conn: &Connection) -> Result<()> {
+        conn.truncate_table(Self::INFO.name).await
+    }
+
+    /// Rename table to `new_name`, entity still refers to the old name
+    async fn rename_table(conn: &Connection, new_name: &str) -> Result<()> {
+        conn.rename_table(Self::INFO.name, new_name).await
+    }
+
     /// Insert builder
     fn insert() -> InsertBuilder<Self> {
         InsertBuilder::new()
     }
 
+    /// Upsert builder
+    fn upsert() -> UpsertBuilder<Self> {
+        UpsertBuilder::new()
+    }
+
     /// Delete builder
     fn delete() -> DeleteBuilder<Self> {
         DeleteBuilder::new()
@@ -39,3 +70,41 @@ pub trait Entity: Sized + Send {
         FindBuilder::new()
     }
 }
+
+/// Tuple of entities, used to init or drop tables of multiple entities
+#[async_trait::async_trait]
+pub trait EntityList {
+    async fn init_all(conn: &Connection) -> Result<()>;
+    async fn drop_all(conn: &Connection) -> Result<()>;
+}
+
+macro_rules! impl_entity_list {
+    ($($name:ident),+) => {
+        #[async_trait::async_trait]
+        impl<$($name: Entity),+> EntityList for ($($name,)+) {
+            async fn init_all(conn: &Connection) -> Result<()> {
+                $($name::init(conn).await?;)+
+
+                Ok(())
+            }
+
+            async fn drop_all(conn: &Connection) -> Result<()> {
+                // Referencing tables are usually listed after referenced tables
+                for name in [$($name::INFO.name),+].into_iter().rev() {
+                    conn.drop_table_if_exists(name).await?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_entity_list! {A}
+impl_entity_list! {A, B}
+impl_entity_list! {A, B, C}
+impl_entity_list! {A, B, C, D}
+impl_entity_list! {A, B, C, D, E}
+impl_entity_list! {A, B, C, D, E, F}
+impl_entity_list! {A, B, C, D, E, F, G}
+impl_entity_list! {A, B, C, D, E, F, G, H}
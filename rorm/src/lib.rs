@@ -1,20 +1,35 @@
 mod builder;
+mod codegen;
 mod connection;
+mod db_enum;
 mod entity;
+mod migration;
 mod model;
 mod repository;
 
 pub use async_trait::async_trait;
 pub use rorm_error as error;
-pub use rorm_macro::Entity;
+pub use rorm_macro::{DbEnum, DbType, Entity};
 pub use rorm_query as query;
 
-pub use builder::{DeleteBuilder, FindBuilder, InsertBuilder, ToSqlParamPair, UpdateBuilder};
+pub use builder::{
+    in_list, DeleteBuilder, FindBuilder, InsertBuilder, ToSqlParamPair, UpdateBuilder,
+    UpsertBuilder, IN_LIST_CHUNK_SIZE,
+};
+pub use codegen::{gen_entities, gen_entities_from_desc};
 pub use connection::Connection;
-pub use entity::Entity;
+pub use db_enum::DbEnum;
+pub use entity::{Entity, EntityList};
+pub use migration::{
+    Migration, MigrationContext, MigrationState, MigrationStatus, Migrator, SqlMigration,
+};
 pub use model::{Model, ModelColumn, ModelColumn::NotSet, ModelColumn::Set};
 pub use repository::Repository;
 pub use rorm_conn::{
-    driver, ColumnInfo, ColumnType, FromValue, IndexInfo, IndexKeyInfo, Row, TableInfo, ToValue,
-    Value,
+    driver, ColumnDesc, ColumnInfo, ColumnType, Date, DateTime, DbType, Decimal, ForeignKeyDesc,
+    FromRow, FromValue, IndexDesc, IndexInfo, IndexKeyInfo, Row, SchemaChange, SchemaDiff,
+    SyncMode, TableDesc, TableInfo, Time, Timestamp, ToValue, Value,
 };
+
+#[cfg(feature = "uuid")]
+pub use rorm_conn::Uuid;
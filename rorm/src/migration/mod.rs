@@ -0,0 +1,509 @@
+//! # Migration
+//!
+//! Versioned migrations, applied migrations are recorded in table `rorm_migrations`
+//! with a checksum, so a modified migration is detected before anything is run.
+
+mod sql;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "runtime-tokio-0.2")]
+use tokio_02::time::delay_for as sleep;
+
+#[cfg(feature = "runtime-tokio-1")]
+use tokio_1::time::sleep;
+
+use crate::{
+    error::{self, Result},
+    query::Dialect,
+    ColumnInfo, ColumnType, Connection, TableInfo, ToValue, Value,
+};
+
+pub use sql::SqlMigration;
+
+const MIGRATION_TABLE: TableInfo = TableInfo {
+    name: "rorm_migrations",
+    columns: &[
+        ColumnInfo {
+            name: "version",
+            ty: ColumnType::U64,
+            is_primary_key: true,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+        ColumnInfo {
+            name: "name",
+            ty: ColumnType::Str(255),
+            is_primary_key: false,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+        ColumnInfo {
+            name: "checksum",
+            ty: ColumnType::Str(64),
+            is_primary_key: false,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+        ColumnInfo {
+            name: "applied_at",
+            ty: ColumnType::U64,
+            is_primary_key: false,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+    ],
+    indexes: &[],
+};
+
+/// Holds at most one row, the row is inserted by the process running migrations
+const LOCK_TABLE: TableInfo = TableInfo {
+    name: "rorm_migrations_lock",
+    columns: &[
+        ColumnInfo {
+            name: "id",
+            ty: ColumnType::U64,
+            is_primary_key: true,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+        ColumnInfo {
+            name: "locked_at",
+            ty: ColumnType::U64,
+            is_primary_key: false,
+            is_not_null: true,
+            is_auto_increment: false,
+            default: None,
+            is_unique: false,
+            flatten_ref: None,
+        },
+    ],
+    indexes: &[],
+};
+
+/// Bookkeeping tables of migrator, not entities of users
+pub(crate) const MIGRATION_TABLES: &[&str] = &[MIGRATION_TABLE.name, LOCK_TABLE.name];
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+#[async_trait::async_trait]
+pub trait Migration: Send + Sync {
+    /// Unique version, migrations are applied in ascending order of version
+    fn version(&self) -> u64;
+
+    fn name(&self) -> &str;
+
+    /// Recorded when applied, and compared with the recorded one on every run, so it must change
+    /// whenever `up` changes, e.g. hash of the sqls, or a revision bumped by hand for code
+    fn checksum(&self) -> String;
+
+    /// Add statements to `ctx`, they are committed with the record of the migration
+    async fn up(&self, ctx: &mut MigrationContext<'_>) -> Result<()>;
+
+    async fn down(&self, _ctx: &mut MigrationContext<'_>) -> Result<()> {
+        Err(error::migration!(
+            "Migration {} `{}` is irreversible",
+            self.version(),
+            self.name()
+        ))
+    }
+}
+
+/// Statements of a migration, run in one transaction with the insert or delete of
+/// `rorm_migrations`, though mysql commits implicitly after ddl
+pub struct MigrationContext<'conn> {
+    conn: &'conn Connection,
+    pairs: Vec<(String, Vec<Vec<Value>>)>,
+}
+
+impl<'conn> MigrationContext<'conn> {
+    fn new(conn: &'conn Connection) -> Self {
+        Self {
+            conn,
+            pairs: vec![],
+        }
+    }
+
+    /// Connection to read data, statements executed by it are not in the transaction
+    pub fn conn(&self) -> &Connection {
+        self.conn
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.conn.dialect()
+    }
+
+    pub fn execute<S: ToString>(&mut self, sql: S, params: Vec<Value>) -> &mut Self {
+        self.pairs.push((sql.to_string(), vec![params]));
+        self
+    }
+
+    async fn commit(self) -> Result<()> {
+        self.conn.execute_many(self.pairs).await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Pending,
+    Applied,
+    /// Applied, but the migration is modified after that
+    ChecksumMismatch,
+    /// Applied, but the migration is not found in migrator
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: u64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<u64>, // Unix timestamp in seconds
+}
+
+struct AppliedMigration {
+    name: String,
+    checksum: String,
+    applied_at: u64,
+}
+
+pub struct Migrator {
+    migrations: Vec<Box<dyn Migration>>,
+    lock_timeout: Duration,
+}
+
+impl Default for Migrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Migrator {
+    pub fn new() -> Self {
+        Self {
+            migrations: vec![],
+            lock_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Load sql migrations from dir, see `SqlMigration::from_dir`
+    pub fn from_dir<P: AsRef<std::path::Path>>(dir: P) -> Result<Self> {
+        let mut migrator = Self::new();
+        for migration in SqlMigration::from_dir(dir)? {
+            migrator = migrator.migration(migration);
+        }
+
+        Ok(migrator)
+    }
+
+    pub fn migration<M: Migration + 'static>(mut self, migration: M) -> Self {
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    /// How long to wait for another process to finish migrating, default 60s
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Migrations sorted by version
+    pub fn migrations(&self) -> Result<Vec<&dyn Migration>> {
+        let mut migrations = self
+            .migrations
+            .iter()
+            .map(|migration| migration.as_ref())
+            .collect::<Vec<_>>();
+        migrations.sort_by_key(|migration| migration.version());
+
+        if let Some(pair) = migrations
+            .windows(2)
+            .find(|pair| pair[0].version() == pair[1].version())
+        {
+            return Err(error::migration!(
+                "Duplicate migration version {}",
+                pair[0].version()
+            ));
+        }
+
+        Ok(migrations)
+    }
+
+    /// State of all known and applied migrations, sorted by version
+    pub async fn status(&self, conn: &Connection) -> Result<Vec<MigrationStatus>> {
+        let migrations = self.migrations()?;
+        let mut applied = self.applied(conn).await?;
+
+        let mut statuses = migrations
+            .iter()
+            .map(|migration| {
+                let record = applied.remove(&migration.version());
+                let state = match &record {
+                    None => MigrationState::Pending,
+                    Some(record) if record.checksum != migration.checksum() => {
+                        MigrationState::ChecksumMismatch
+                    }
+                    Some(_) => MigrationState::Applied,
+                };
+
+                MigrationStatus {
+                    version: migration.version(),
+                    name: migration.name().to_string(),
+                    state,
+                    applied_at: record.map(|record| record.applied_at),
+                }
+            })
+            .collect::<Vec<_>>();
+        statuses.extend(
+            applied
+                .into_iter()
+                .map(|(version, record)| MigrationStatus {
+                    version,
+                    name: record.name,
+                    state: MigrationState::Missing,
+                    applied_at: Some(record.applied_at),
+                }),
+        );
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+
+    /// Apply all pending migrations, return versions applied
+    pub async fn migrate_up(&self, conn: &Connection) -> Result<Vec<u64>> {
+        self.lock(conn).await?;
+        let result = self.migrate_up_locked(conn).await;
+        let unlocked = self.unlock(conn).await;
+
+        let versions = result?;
+        unlocked?;
+        Ok(versions)
+    }
+
+    /// Revert applied migrations newer than `target` in descending order,
+    /// return versions reverted, `0` reverts all
+    pub async fn migrate_down(&self, conn: &Connection, target: u64) -> Result<Vec<u64>> {
+        self.lock(conn).await?;
+        let result = self.migrate_down_locked(conn, target).await;
+        let unlocked = self.unlock(conn).await;
+
+        let versions = result?;
+        unlocked?;
+        Ok(versions)
+    }
+
+    /// Release the lock left by a dead process
+    pub async fn force_unlock(&self, conn: &Connection) -> Result<()> {
+        conn.init_table(&LOCK_TABLE).await?;
+        self.unlock(conn).await
+    }
+
+    async fn migrate_up_locked(&self, conn: &Connection) -> Result<Vec<u64>> {
+        let migrations = self.migrations()?;
+        let applied = self.applied(conn).await?;
+        verify(&migrations, &applied)?;
+
+        let mut versions = vec![];
+        for migration in migrations {
+            if applied.contains_key(&migration.version()) {
+                continue;
+            }
+
+            let apply = async {
+                let mut ctx = MigrationContext::new(conn);
+                migration.up(&mut ctx).await?;
+                ctx.execute(
+                    "INSERT INTO rorm_migrations (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)",
+                    vec![
+                        migration.version().to_value(),
+                        migration.name().to_string().to_value(),
+                        migration.checksum().to_value(),
+                        now().to_value(),
+                    ],
+                );
+                ctx.commit().await
+            };
+            apply.await.map_err(|e| {
+                error::migration!(
+                    "Apply migration {} `{}` error: {}",
+                    migration.version(),
+                    migration.name(),
+                    e
+                )
+            })?;
+            versions.push(migration.version());
+        }
+
+        Ok(versions)
+    }
+
+    async fn migrate_down_locked(&self, conn: &Connection, target: u64) -> Result<Vec<u64>> {
+        let migrations = self.migrations()?;
+        let applied = self.applied(conn).await?;
+        verify(&migrations, &applied)?;
+
+        let mut versions = vec![];
+        for migration in migrations.into_iter().rev() {
+            if migration.version() <= target || !applied.contains_key(&migration.version()) {
+                continue;
+            }
+
+            let revert = async {
+                let mut ctx = MigrationContext::new(conn);
+                migration.down(&mut ctx).await?;
+                ctx.execute(
+                    "DELETE FROM rorm_migrations WHERE version = ?",
+                    vec![migration.version().to_value()],
+                );
+                ctx.commit().await
+            };
+            revert.await.map_err(|e| {
+                error::migration!(
+                    "Revert migration {} `{}` error: {}",
+                    migration.version(),
+                    migration.name(),
+                    e
+                )
+            })?;
+            versions.push(migration.version());
+        }
+
+        Ok(versions)
+    }
+
+    async fn applied(&self, conn: &Connection) -> Result<HashMap<u64, AppliedMigration>> {
+        conn.init_table(&MIGRATION_TABLE).await?;
+
+        let records = conn
+            .query_many_map(
+                "SELECT version, name, checksum, applied_at FROM rorm_migrations",
+                vec![],
+                |row| async move {
+                    Ok((
+                        row.get::<u64>("version")?,
+                        AppliedMigration {
+                            name: row.get::<String>("name")?,
+                            checksum: row.get::<String>("checksum")?,
+                            applied_at: row.get::<u64>("applied_at")?,
+                        },
+                    ))
+                },
+            )
+            .await?;
+
+        Ok(records.into_iter().collect())
+    }
+
+    /// Insert the lock row, wait while it's held by another process
+    async fn lock(&self, conn: &Connection) -> Result<()> {
+        conn.init_table(&LOCK_TABLE).await?;
+
+        let start = Instant::now();
+        loop {
+            let e = match conn
+                .execute_one(
+                    "INSERT INTO rorm_migrations_lock (id, locked_at) VALUES (1, ?)",
+                    vec![now().to_value()],
+                )
+                .await
+            {
+                Ok(_) => return Ok(()),
+                Err(e) => e,
+            };
+
+            // Insert fails for other reasons if lock row doesn't exist
+            let locked_at = conn
+                .query_many_map(
+                    "SELECT locked_at FROM rorm_migrations_lock WHERE id = 1",
+                    vec![],
+                    |row| async move { row.get::<u64>("locked_at") },
+                )
+                .await?;
+            let locked_at = match locked_at.first() {
+                Some(locked_at) => *locked_at,
+                None => return Err(e),
+            };
+
+            if start.elapsed() >= self.lock_timeout {
+                return Err(error::timeout!(
+                    "Migration is locked by another process since {}, use `force_unlock` if it's dead",
+                    locked_at
+                ));
+            }
+            sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+
+    async fn unlock(&self, conn: &Connection) -> Result<()> {
+        conn.execute_one("DELETE FROM rorm_migrations_lock WHERE id = 1", vec![])
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Every applied migration must be known and unchanged
+fn verify(migrations: &[&dyn Migration], applied: &HashMap<u64, AppliedMigration>) -> Result<()> {
+    let mut versions = applied.keys().copied().collect::<Vec<_>>();
+    versions.sort_unstable();
+
+    for version in versions {
+        let record = &applied[&version];
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.version() == version)
+            .ok_or_else(|| {
+                error::migration!(
+                    "Applied migration {} `{}` is not found",
+                    version,
+                    record.name
+                )
+            })?;
+
+        if migration.checksum() != record.checksum {
+            return Err(error::migration!(
+                "Checksum mismatch of applied migration {} `{}`",
+                version,
+                record.name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// 64-bit FNV-1a in hex, stable across platforms and compiler versions
+pub(crate) fn checksum(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,209 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::{self, Result},
+    query::Dialect,
+};
+
+use super::{checksum, Migration, MigrationContext};
+
+/// Migration written in sql, statements are separated by `;`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlMigration {
+    version: u64,
+    name: String,
+    up: String,
+    down: Option<String>,
+}
+
+impl SqlMigration {
+    pub fn new<N: Into<String>, S: Into<String>>(version: u64, name: N, up: S) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            up: up.into(),
+            down: None,
+        }
+    }
+
+    pub fn down<S: Into<String>>(mut self, sql: S) -> Self {
+        self.down = Some(sql.into());
+        self
+    }
+
+    /// Load migrations from files named `<version>_<name>.up.sql` and the optional
+    /// `<version>_<name>.down.sql`, other files are ignored
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| error::migration!("Read dir `{}` error: {}", dir.display(), e))?;
+
+        let mut ups = vec![];
+        let mut downs = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|e| error::migration!("Read dir `{}` error: {}", dir.display(), e))?
+                .path();
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+
+            let (stem, is_up) = if let Some(stem) = file_name.strip_suffix(".up.sql") {
+                (stem, true)
+            } else if let Some(stem) = file_name.strip_suffix(".down.sql") {
+                (stem, false)
+            } else {
+                continue;
+            };
+            let (version, name) = parse_stem(stem)
+                .ok_or_else(|| error::migration!("Invalid migration file name `{}`", file_name))?;
+            let sql = fs::read_to_string(&path)
+                .map_err(|e| error::migration!("Read `{}` error: {}", path.display(), e))?;
+
+            if is_up {
+                ups.push(Self::new(version, name, sql));
+            } else {
+                downs.push((version, name.to_string(), sql));
+            }
+        }
+
+        for (version, name, sql) in downs {
+            let up = ups
+                .iter_mut()
+                .find(|up| up.version == version && up.name == name)
+                .ok_or_else(|| {
+                    error::migration!("Up migration of `{}_{}` is not found", version, name)
+                })?;
+            up.down = Some(sql);
+        }
+        ups.sort_by_key(|up| up.version);
+
+        Ok(ups)
+    }
+
+    fn run(&self, ctx: &mut MigrationContext<'_>, sql: &str) {
+        for stmt in split_statements(sql, ctx.dialect()) {
+            ctx.execute(stmt, vec![]);
+        }
+    }
+
+    fn irreversible(&self) -> error::Error {
+        error::migration!("Migration {} `{}` is irreversible", self.version, self.name)
+    }
+}
+
+#[async_trait::async_trait]
+impl Migration for SqlMigration {
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn checksum(&self) -> String {
+        checksum(self.up.as_bytes())
+    }
+
+    async fn up(&self, ctx: &mut MigrationContext<'_>) -> Result<()> {
+        self.run(ctx, &self.up);
+        Ok(())
+    }
+
+    async fn down(&self, ctx: &mut MigrationContext<'_>) -> Result<()> {
+        match &self.down {
+            Some(sql) => {
+                self.run(ctx, sql);
+                Ok(())
+            }
+            None => Err(self.irreversible()),
+        }
+    }
+}
+
+/// `20220101000000_create_user` => (20220101000000, "create_user")
+fn parse_stem(stem: &str) -> Option<(u64, &str)> {
+    let (version, name) = stem.split_once('_')?;
+    let version = version.parse().ok()?;
+
+    Some((version, name))
+}
+
+/// Split sql by `;` outside of quotes, comments are dropped. Backslash escapes quote in strings
+/// of mysql, e.g. `'a\';b'`
+fn split_statements(sql: &str, dialect: Dialect) -> Vec<String> {
+    let mut stmts = vec![];
+    let mut stmt = String::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                stmt.push(c);
+                let is_escapable = dialect == Dialect::Mysql && c != '`';
+                while let Some(inner) = chars.next() {
+                    stmt.push(inner);
+                    if inner == '\\' && is_escapable {
+                        stmt.extend(chars.next());
+                    } else if inner == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for inner in chars.by_ref() {
+                    if inner == '\n' {
+                        stmt.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for inner in chars.by_ref() {
+                    if prev == '*' && inner == '/' {
+                        break;
+                    }
+                    prev = inner;
+                }
+                stmt.push(' ');
+            }
+            ';' => stmts.push(std::mem::take(&mut stmt)),
+            _ => stmt.push(c),
+        }
+    }
+    stmts.push(stmt);
+
+    stmts
+        .into_iter()
+        .map(|stmt| stmt.trim().to_string())
+        .filter(|stmt| !stmt.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "INSERT INTO t VALUES ('a;b'); -- c;\nSELECT `x;y` /* ; */ FROM t;";
+        let stmts = vec!["INSERT INTO t VALUES ('a;b')", "SELECT `x;y`   FROM t"];
+        assert_eq!(split_statements(sql, Dialect::Sqlite), stmts);
+        assert_eq!(split_statements(sql, Dialect::Mysql), stmts);
+
+        // Backslash escapes quote only in mysql
+        let sql = r"INSERT INTO t VALUES ('a\';b'); SELECT 1";
+        assert_eq!(
+            split_statements(sql, Dialect::Mysql),
+            vec![r"INSERT INTO t VALUES ('a\';b')", "SELECT 1"]
+        );
+        assert_eq!(
+            split_statements(r"SELECT 'a\'; SELECT 1", Dialect::Sqlite),
+            vec![r"SELECT 'a\'", "SELECT 1"]
+        );
+    }
+}
@@ -1,35 +1,58 @@
 use crate::{
-    query::{and, eq, Where},
+    query::{and, eq, is_null, Where},
     ToValue, Value,
 };
 
 pub trait Model<K>: Sized {
     fn into_set_pairs(self) -> Vec<(&'static str, Value)>; // (column, value)
 
-    fn to_primary_key(id: u64) -> K;
+    /// Primary key from the inserted id of database, none if the key is not converted from id
+    fn to_primary_key(id: u64) -> Option<K>;
 
+    /// Fill primary key generated by client (e.g. uuid) if it's not set, and return it
+    /// instead of the inserted id of database
+    fn gen_primary_key(&mut self) -> Option<K> {
+        None
+    }
+
+    /// Null values are matched by `IS NULL`, other values are bound as params
     fn gen_where_and_params(self) -> (Option<Where>, Vec<Value>) {
-        let pairs = self.into_set_pairs();
-        if pairs.is_empty() {
-            (None, vec![])
-        } else {
-            let mut params = vec![];
-            let mut cond = None;
+        gen_where_and_params(self.into_set_pairs(), None)
+    }
 
-            for (col, value) in pairs {
-                let c = eq!(col, "?");
-                cond = if let Some(cond) = cond {
-                    Some(and!(cond, c))
-                } else {
-                    Some(c)
-                };
+    /// Same as `gen_where_and_params`, but columns are qualified by table, e.g. `user.id`,
+    /// so the condition stays unambiguous in joins
+    fn gen_qualified_where_and_params(self, table: &str) -> (Option<Where>, Vec<Value>) {
+        gen_where_and_params(self.into_set_pairs(), Some(table))
+    }
+}
 
-                params.push(value);
-            }
+fn gen_where_and_params(
+    pairs: Vec<(&'static str, Value)>,
+    table: Option<&str>,
+) -> (Option<Where>, Vec<Value>) {
+    let mut params = vec![];
+    let mut cond = None;
 
-            (cond, params)
-        }
+    for (col, value) in pairs {
+        let col = match table {
+            Some(table) => format!("{}.{}", table, col),
+            None => col.to_string(),
+        };
+        let c = if let Value::Null = value {
+            is_null!(col)
+        } else {
+            params.push(value);
+            eq!(col, "?")
+        };
+        cond = if let Some(cond) = cond {
+            Some(and!(cond, c))
+        } else {
+            Some(c)
+        };
     }
+
+    (cond, params)
 }
 
 #[derive(Debug)]
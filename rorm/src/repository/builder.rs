@@ -1,6 +1,8 @@
 use crate::{
-    error::Result, query::Where, Connection, DeleteBuilder, Entity, FindBuilder, InsertBuilder,
-    UpdateBuilder, Value,
+    error::Result,
+    query::{self, JoinKind, Where},
+    ColumnType, Connection, DeleteBuilder, Entity, FindBuilder, FromRow, FromValue, InsertBuilder,
+    ToValue, UpdateBuilder, UpsertBuilder, Value,
 };
 
 pub struct RepoInsertBuilder<E: Entity> {
@@ -50,6 +52,69 @@ impl<E: Entity> RepoInsertBuilder<E> {
     }
 }
 
+pub struct RepoUpsertBuilder<E: Entity> {
+    conn: Connection,
+    builder: UpsertBuilder<E>,
+}
+
+impl<E: Entity> RepoUpsertBuilder<E> {
+    pub fn new(conn: Connection) -> Self {
+        Self {
+            conn,
+            builder: UpsertBuilder::new(),
+        }
+    }
+
+    pub fn model<I>(mut self, model: I) -> Self
+    where
+        I: Into<E::Model>,
+    {
+        self.builder = self.builder.model(model);
+        self
+    }
+
+    pub fn models<L, I>(mut self, models: L) -> Self
+    where
+        L: IntoIterator<Item = I>,
+        I: Into<E::Model>,
+    {
+        self.builder = self.builder.models(models);
+        self
+    }
+
+    pub fn on_conflict<I, S>(mut self, target: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.builder = self.builder.on_conflict(target);
+        self
+    }
+
+    pub fn update<I, S>(mut self, cols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.builder = self.builder.update(cols);
+        self
+    }
+
+    pub fn set(mut self, col: &str, expr: query::QueryValue, params: Vec<Value>) -> Self {
+        self.builder = self.builder.set(col, expr, params);
+        self
+    }
+
+    pub fn do_nothing(mut self) -> Self {
+        self.builder = self.builder.do_nothing();
+        self
+    }
+
+    pub async fn execute(self) -> Result<()> {
+        self.builder.execute(&self.conn).await
+    }
+}
+
 pub struct RepoDeleteBuilder<E: Entity> {
     conn: Connection,
     builder: DeleteBuilder<E>,
@@ -124,6 +189,16 @@ impl<E: Entity> RepoUpdateBuilder<E> {
         self
     }
 
+    pub fn set(mut self, col: &str, expr: query::QueryValue, params: Vec<Value>) -> Self {
+        self.builder = self.builder.set(col, expr, params);
+        self
+    }
+
+    pub fn increment<V: ToValue>(mut self, col: &str, by: V) -> Self {
+        self.builder = self.builder.increment(col, by);
+        self
+    }
+
     pub fn filter_model<I>(mut self, model: I) -> Self
     where
         I: Into<E::Model>,
@@ -190,12 +265,53 @@ impl<E: Entity> RepoFindBuilder<E> {
         self
     }
 
-    pub fn group_by(mut self, col: &str) -> Self {
+    pub fn filter_in<S, I, T>(mut self, col: S, values: I) -> Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = T>,
+        T: ToValue,
+    {
+        self.builder = self.builder.filter_in(col, values);
+        self
+    }
+
+    pub fn inner_join<S: ToString>(mut self, table: S, on: Where, params: Vec<Value>) -> Self {
+        self.builder = self.builder.inner_join(table, on, params);
+        self
+    }
+
+    pub fn left_join<S: ToString>(mut self, table: S, on: Where, params: Vec<Value>) -> Self {
+        self.builder = self.builder.left_join(table, on, params);
+        self
+    }
+
+    pub fn join<S: ToString>(
+        mut self,
+        kind: JoinKind,
+        table: S,
+        on: Option<Where>,
+        params: Vec<Value>,
+    ) -> Self {
+        self.builder = self.builder.join(kind, table, on, params);
+        self
+    }
+
+    pub fn group_by<S: Into<query::Expr>>(mut self, col: S) -> Self {
         self.builder = self.builder.group_by(col);
         self
     }
 
-    pub fn order_by(mut self, col: &str, is_asc: bool) -> Self {
+    pub fn having(mut self, cond: Where, params: Vec<Value>) -> Self {
+        self.builder = self.builder.having(cond, params);
+        self
+    }
+
+    pub fn distinct(mut self) -> Self {
+        self.builder = self.builder.distinct();
+        self
+    }
+
+    pub fn order_by<S: Into<query::Expr>>(mut self, col: S, is_asc: bool) -> Self {
         self.builder = self.builder.order_by(col, is_asc);
         self
     }
@@ -219,4 +335,72 @@ impl<E: Entity> RepoFindBuilder<E> {
     pub async fn all(self) -> Result<Vec<E>> {
         Ok(self.builder.execute(&self.conn).await?)
     }
+
+    /// Rows of selected columns, e.g. `select::<(String, i64), _, _>(["city", "COUNT(*)"])`
+    pub async fn select<T, I, S>(self, columns: I) -> Result<Vec<T>>
+    where
+        T: FromRow,
+        I: IntoIterator<Item = S>,
+        S: Into<query::Expr>,
+    {
+        self.builder.select(&self.conn, columns).await
+    }
+
+    pub async fn count(self) -> Result<u64> {
+        self.builder.count(&self.conn).await
+    }
+
+    pub async fn exists(self) -> Result<bool> {
+        self.builder.exists(&self.conn).await
+    }
+
+    /// Null if no row is matched, e.g. `sum::<Option<i64>>("amount")`
+    pub async fn sum<T: FromValue<Output = T>>(self, col: &str) -> Result<T> {
+        let col = self.numeric_column(col);
+        self.builder.aggregate(&self.conn, query::sum!(col)).await
+    }
+
+    pub async fn avg<T: FromValue<Output = T>>(self, col: &str) -> Result<T> {
+        let col = self.numeric_column(col);
+        self.builder.aggregate(&self.conn, query::avg!(col)).await
+    }
+
+    pub async fn min<T: FromValue<Output = T>>(self, col: &str) -> Result<T> {
+        let agg = self.u64_value(col, query::min!(col));
+        self.builder.aggregate(&self.conn, agg).await
+    }
+
+    pub async fn max<T: FromValue<Output = T>>(self, col: &str) -> Result<T> {
+        let agg = self.u64_value(col, query::max!(col));
+        self.builder.aggregate(&self.conn, agg).await
+    }
+
+    /// Sqlite stores u64 above i64::MAX as blob, which is treated as 0 by arithmetic,
+    /// so it is checked by `u64_number` to fail instead
+    fn numeric_column(&self, col: &str) -> String {
+        if self.is_sqlite_u64(col) {
+            format!("u64_number({})", col)
+        } else {
+            col.to_string()
+        }
+    }
+
+    /// Blob of u64 returned by `MIN` or `MAX` has no declared type in sqlite,
+    /// so it is read by `u64_value`
+    fn u64_value(&self, col: &str, agg: query::Aggregate) -> query::Expr {
+        if self.is_sqlite_u64(col) {
+            query::func!("u64_value", agg)
+        } else {
+            agg.into()
+        }
+    }
+
+    fn is_sqlite_u64(&self, col: &str) -> bool {
+        let is_u64 = E::INFO
+            .columns
+            .iter()
+            .any(|c| c.name == col && c.ty == ColumnType::U64);
+
+        is_u64 && self.conn.dialect() == query::Dialect::Sqlite
+    }
 }
@@ -2,9 +2,11 @@ mod builder;
 
 use std::marker::PhantomData;
 
-use crate::{error::Result, Connection, Entity};
+use crate::{error::Result, Connection, Entity, SchemaDiff, SyncMode, ToValue};
 
-use builder::{RepoDeleteBuilder, RepoFindBuilder, RepoInsertBuilder, RepoUpdateBuilder};
+use builder::{
+    RepoDeleteBuilder, RepoFindBuilder, RepoInsertBuilder, RepoUpdateBuilder, RepoUpsertBuilder,
+};
 
 #[derive(Clone)]
 pub struct Repository<E: Entity> {
@@ -28,11 +30,41 @@ impl<E: Entity> Repository<E> {
         Ok(())
     }
 
+    #[inline]
+    pub async fn init_with(&self, mode: SyncMode) -> Result<SchemaDiff> {
+        E::init_with(&self.conn, mode).await
+    }
+
+    #[inline]
+    pub async fn drop_table(&self) -> Result<()> {
+        E::drop_table(&self.conn).await
+    }
+
+    #[inline]
+    pub async fn drop_if_exists(&self) -> Result<()> {
+        E::drop_if_exists(&self.conn).await
+    }
+
+    #[inline]
+    pub async fn truncate(&self) -> Result<()> {
+        E::truncate(&self.conn).await
+    }
+
+    #[inline]
+    pub async fn rename_table(&self, new_name: &str) -> Result<()> {
+        E::rename_table(&self.conn, new_name).await
+    }
+
     #[inline]
     pub fn insert(&self) -> RepoInsertBuilder<E> {
         RepoInsertBuilder::new(self.conn.clone())
     }
 
+    #[inline]
+    pub fn upsert(&self) -> RepoUpsertBuilder<E> {
+        RepoUpsertBuilder::new(self.conn.clone())
+    }
+
     #[inline]
     pub fn delete(&self) -> RepoDeleteBuilder<E> {
         RepoDeleteBuilder::new(self.conn.clone())
@@ -43,6 +75,13 @@ impl<E: Entity> Repository<E> {
         RepoUpdateBuilder::new(self.conn.clone())
     }
 
+    /// Shortcut of `update().increment(col, by)` for counters, e.g.
+    /// `repo.increment("views", 1).filter_model(id).one().await?`
+    #[inline]
+    pub fn increment<V: ToValue>(&self, col: &str, by: V) -> RepoUpdateBuilder<E> {
+        self.update().increment(col, by)
+    }
+
     #[inline]
     pub fn find(&self) -> RepoFindBuilder<E> {
         RepoFindBuilder::new(self.conn.clone())